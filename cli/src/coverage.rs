@@ -0,0 +1,377 @@
+//! Implementation of the `boa coverage` subcommand.
+//!
+//! Runs the given files with the VM code coverage collection enabled, and outputs the result
+//! in the LCOV tracefile format or as Istanbul JSON coverage data.
+//!
+//! Boa does not support source maps, so the coverage is always reported against the executed
+//! files themselves. Code without a source file, like `eval` code, is not reported.
+
+use crate::{Opt, evaluate_file, logger::SharedExternalPrinterLogger};
+use boa_engine::{
+    Context,
+    ast::Span,
+    module::SimpleModuleLoader,
+    vm::{
+        SourcePath,
+        coverage::{Coverage, FunctionCoverage},
+    },
+};
+use clap::{Args, ValueHint};
+use color_eyre::{Result, eyre::WrapErr};
+use serde_json::{Map, Value, json};
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf};
+
+#[derive(Debug, Args)]
+pub(crate) struct CoverageArgs {
+    /// The JavaScript file(s) to collect the coverage of.
+    #[arg(name = "FILE", value_hint = ValueHint::FilePath, required = true)]
+    files: Vec<PathBuf>,
+
+    /// Write the coverage in the LCOV tracefile format to the given file.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    lcov: Option<PathBuf>,
+
+    /// Write the coverage as Istanbul JSON coverage data to the given file.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    istanbul: Option<PathBuf>,
+}
+
+/// Runs the `coverage` subcommand.
+///
+/// If no output file is given, the LCOV tracefile is printed to stdout. The coverage is also
+/// reported when a file throws, in which case the error is returned afterwards.
+pub(crate) fn run(
+    coverage_args: &CoverageArgs,
+    args: &Opt,
+    context: &mut Context,
+    loader: &SimpleModuleLoader,
+    printer: &SharedExternalPrinterLogger,
+) -> Result<()> {
+    context.start_coverage();
+    let result = coverage_args
+        .files
+        .iter()
+        .try_for_each(|file| evaluate_file(file, args, context, loader, printer));
+    let coverage = context.stop_coverage().unwrap_or_default();
+
+    if let Some(path) = &coverage_args.lcov {
+        fs::write(path, to_lcov(&coverage))
+            .wrap_err_with(|| format!("could not write `{}`", path.display()))?;
+    }
+
+    if let Some(path) = &coverage_args.istanbul {
+        let json = serde_json::to_string_pretty(&to_istanbul(&coverage))?;
+        fs::write(path, json).wrap_err_with(|| format!("could not write `{}`", path.display()))?;
+    }
+
+    if coverage_args.lcov.is_none() && coverage_args.istanbul.is_none() {
+        print!("{}", to_lcov(&coverage));
+    }
+
+    result
+}
+
+/// Groups the functions of the coverage by source file.
+fn files(coverage: &Coverage) -> BTreeMap<String, Vec<&FunctionCoverage>> {
+    let mut files = BTreeMap::<_, Vec<_>>::new();
+    for function in coverage.functions() {
+        if let SourcePath::Path(path) = function.path() {
+            files
+                .entry(path.display().to_string())
+                .or_default()
+                .push(function);
+        }
+    }
+    files
+}
+
+/// Returns the names of the functions of a file, which are unique within the file.
+///
+/// Anonymous functions are named after their index in the file. The index is also appended to
+/// the names shared by several functions, like methods of different classes, since coverage
+/// tools merge the counts of functions with the same name.
+fn function_names(functions: &[&FunctionCoverage]) -> Vec<String> {
+    let names = functions
+        .iter()
+        .map(|function| function.name().to_std_string_escaped())
+        .collect::<Vec<_>>();
+
+    let mut occurrences = BTreeMap::<&str, usize>::new();
+    for name in &names {
+        *occurrences.entry(name).or_default() += 1;
+    }
+
+    names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            if name.is_empty() {
+                format!("(anonymous_{index})")
+            } else if occurrences[name.as_str()] > 1 {
+                format!("{name} ({index})")
+            } else {
+                name.clone()
+            }
+        })
+        .collect()
+}
+
+/// Converts the coverage to the LCOV tracefile format.
+///
+/// The top level code of scripts and modules only contributes lines, not functions.
+fn to_lcov(coverage: &Coverage) -> String {
+    let mut lcov = String::new();
+    for (path, functions) in files(coverage) {
+        let _ = writeln!(lcov, "TN:");
+        let _ = writeln!(lcov, "SF:{path}");
+
+        let mut functions_found = 0;
+        let mut functions_hit = 0;
+        for (function, name) in functions.iter().zip(function_names(&functions)) {
+            if function.is_top_level() {
+                continue;
+            }
+            functions_found += 1;
+            let line = function
+                .range()
+                .map_or(0, |range| range.start().line_number());
+            let _ = writeln!(lcov, "FN:{line},{name}");
+            let _ = writeln!(lcov, "FNDA:{},{name}", function.count());
+            if function.count() != 0 {
+                functions_hit += 1;
+            }
+        }
+        let _ = writeln!(lcov, "FNF:{functions_found}");
+        let _ = writeln!(lcov, "FNH:{functions_hit}");
+
+        let mut lines = BTreeMap::<u32, u64>::new();
+        for (line, count) in functions.iter().flat_map(|function| function.lines()) {
+            let entry = lines.entry(*line).or_default();
+            *entry = (*entry).max(*count);
+        }
+        for (line, count) in &lines {
+            let _ = writeln!(lcov, "DA:{line},{count}");
+        }
+        let _ = writeln!(lcov, "LF:{}", lines.len());
+        let _ = writeln!(
+            lcov,
+            "LH:{}",
+            lines.values().filter(|count| **count != 0).count()
+        );
+
+        let _ = writeln!(lcov, "end_of_record");
+    }
+    lcov
+}
+
+/// Converts a source range to an Istanbul location, which has zero based columns.
+fn location(range: Span) -> Value {
+    json!({
+        "start": {
+            "line": range.start().line_number(),
+            "column": range.start().column_number().saturating_sub(1),
+        },
+        "end": {
+            "line": range.end().line_number(),
+            "column": range.end().column_number().saturating_sub(1),
+        },
+    })
+}
+
+/// Converts the coverage to Istanbul JSON coverage data, keyed by source file.
+///
+/// Every basic block is reported as a statement. The top level code of scripts and modules
+/// only contributes statements, not functions. There is no source position for the declaration
+/// of a function, so its `decl` is the position where the code of the function starts.
+fn to_istanbul(coverage: &Coverage) -> Value {
+    let mut result = Map::new();
+    for (path, functions) in files(coverage) {
+        let mut statement_map = Map::new();
+        let mut statements = Map::new();
+        let mut fn_map = Map::new();
+        let mut fns = Map::new();
+
+        for (function, name) in functions.iter().zip(function_names(&functions)) {
+            if let Some(range) = function.range()
+                && !function.is_top_level()
+            {
+                let key = fn_map.len().to_string();
+                fn_map.insert(
+                    key.clone(),
+                    json!({
+                        "name": name,
+                        "decl": location(Span::new(range.start(), range.start())),
+                        "loc": location(range),
+                        "line": range.start().line_number(),
+                    }),
+                );
+                fns.insert(key, function.count().into());
+            }
+
+            for block in function.blocks() {
+                let Some(range) = block.range() else {
+                    continue;
+                };
+                let key = statement_map.len().to_string();
+                statement_map.insert(key.clone(), location(range));
+                statements.insert(key, block.count().into());
+            }
+        }
+
+        result.insert(
+            path.clone(),
+            json!({
+                "path": path,
+                "statementMap": statement_map,
+                "fnMap": fn_map,
+                "branchMap": {},
+                "s": statements,
+                "f": fns,
+                "b": {},
+            }),
+        );
+    }
+    result.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_istanbul, to_lcov};
+    use boa_engine::{Context, Source, vm::coverage::Coverage};
+    use serde_json::json;
+    use std::path::Path;
+
+    const SCRIPT: &str = r"function inner(n) {
+    let s = 0;
+    for (let i = 0; i < n; i++) {
+        s += i;
+    }
+    return s;
+}
+function unused() {
+    return 1;
+}
+inner(3);
+";
+
+    fn coverage_of(script: &str) -> Coverage {
+        let context = &mut Context::default();
+        context.start_coverage();
+        context
+            .eval(Source::from_bytes(script).with_path(Path::new("test.js")))
+            .unwrap();
+        context.stop_coverage().unwrap()
+    }
+
+    fn coverage() -> Coverage {
+        coverage_of(SCRIPT)
+    }
+
+    #[test]
+    fn lcov() {
+        let expected = "\
+TN:
+SF:test.js
+FN:1,inner
+FNDA:1,inner
+FN:8,unused
+FNDA:0,unused
+FNF:2
+FNH:1
+DA:1,1
+DA:2,1
+DA:3,4
+DA:4,3
+DA:6,1
+DA:8,0
+DA:9,0
+DA:11,1
+LF:8
+LH:6
+end_of_record
+";
+        assert_eq!(to_lcov(&coverage()), expected);
+    }
+
+    #[test]
+    fn istanbul() {
+        let istanbul = to_istanbul(&coverage());
+        let file = &istanbul["test.js"];
+
+        assert_eq!(file["path"], "test.js");
+        assert_eq!(file["f"], json!({ "0": 1, "1": 0 }));
+        assert_eq!(file["fnMap"]["0"]["name"], "inner");
+        assert_eq!(file["fnMap"]["1"]["name"], "unused");
+        assert_eq!(
+            file["fnMap"]["0"]["loc"],
+            json!({
+                "start": { "line": 1, "column": 18 },
+                "end": { "line": 6, "column": 11 },
+            })
+        );
+        assert_eq!(
+            file["fnMap"]["0"]["decl"],
+            json!({
+                "start": { "line": 1, "column": 18 },
+                "end": { "line": 1, "column": 18 },
+            })
+        );
+
+        assert_eq!(
+            file["s"],
+            json!({ "0": 1, "1": 3, "2": 4, "3": 3, "4": 1, "5": 0, "6": 1 })
+        );
+        assert_eq!(
+            file["statementMap"]["2"],
+            json!({
+                "start": { "line": 3, "column": 20 },
+                "end": { "line": 3, "column": 20 },
+            })
+        );
+        assert_eq!(
+            file["statementMap"]["6"],
+            json!({
+                "start": { "line": 11, "column": 5 },
+                "end": { "line": 11, "column": 5 },
+            })
+        );
+    }
+
+    #[test]
+    fn lcov_function_names_are_unique() {
+        let coverage = coverage_of(
+            r"function a() {
+    function m() { return 1; }
+    return m();
+}
+function b() {
+    function m() { return 2; }
+    return m;
+}
+a();
+b();
+",
+        );
+        let lcov = to_lcov(&coverage);
+        let functions = lcov
+            .lines()
+            .filter(|line| line.starts_with("FN"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            functions,
+            [
+                "FN:1,a",
+                "FNDA:1,a",
+                "FN:2,m (1)",
+                "FNDA:1,m (1)",
+                "FN:5,b",
+                "FNDA:1,b",
+                "FN:6,m (3)",
+                "FNDA:0,m (3)",
+                "FNF:4",
+                "FNH:3",
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::profile::DEFAULT_SAMPLE_INTERVAL;
 use boa_engine::{Context, Finalize, JsResult, Trace};
 use boa_runtime::{ConsoleState, Logger};
 use rustyline::ExternalPrinter;
@@ -9,12 +10,17 @@ use std::sync::{Arc, Mutex};
 pub(crate) struct SharedExternalPrinterLogger {
     #[unsafe_ignore_trace]
     inner: Arc<Mutex<Option<Box<dyn ExternalPrinter + Send>>>>,
+
+    /// The label of the profile started by `console.profile`, if the profiler was started by it.
+    #[unsafe_ignore_trace]
+    console_profile: Arc<Mutex<Option<String>>>,
 }
 
 impl SharedExternalPrinterLogger {
     pub(crate) fn new() -> Self {
         Self {
             inner: Arc::new(Mutex::new(None)),
+            console_profile: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -65,4 +71,63 @@ impl Logger for SharedExternalPrinterLogger {
         self.print(format!("{msg:>indent$}\n"));
         Ok(())
     }
+
+    fn profile(
+        &self,
+        label: Option<String>,
+        state: &ConsoleState,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let label = label.unwrap_or_default();
+        if context.is_profiling() {
+            return self.warn(
+                format!("Profile '{label}' not started, the profiler is already running"),
+                state,
+                context,
+            );
+        }
+
+        context.start_profiler(DEFAULT_SAMPLE_INTERVAL);
+        self.info(format!("Profile '{label}' started"), state, context)?;
+        *self.console_profile.lock().expect("profile lock failed") = Some(label);
+        Ok(())
+    }
+
+    fn profile_end(
+        &self,
+        label: Option<String>,
+        state: &ConsoleState,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        let active = self
+            .console_profile
+            .lock()
+            .expect("profile lock failed")
+            .clone();
+
+        // Don't stop a profile that was started by `boa profile`.
+        let Some(active) = active else {
+            let label = label.unwrap_or_default();
+            let message = if context.is_profiling() {
+                format!("Profile '{label}' not finished, the profiler is owned by `boa profile`")
+            } else {
+                format!("Profile '{label}' is not running")
+            };
+            return self.warn(message, state, context);
+        };
+
+        // Without a label, the running profile is stopped.
+        let label = label.unwrap_or_else(|| active.clone());
+        if label != active {
+            return self.warn(format!("Profile '{label}' doesn't exist"), state, context);
+        }
+
+        *self.console_profile.lock().expect("profile lock failed") = None;
+        let profile = context.stop_profiler().unwrap_or_default();
+        self.info(format!("Profile '{label}' finished"), state, context)?;
+        for line in profile.to_folded().lines() {
+            self.info(line.to_string(), state, context)?;
+        }
+        Ok(())
+    }
 }
@@ -7,9 +7,11 @@
 #![cfg_attr(not(test), deny(clippy::unwrap_used))]
 #![allow(clippy::print_stdout, clippy::print_stderr)]
 
+mod coverage;
 mod debug;
 mod helper;
 mod logger;
+mod profile;
 
 use crate::logger::SharedExternalPrinterLogger;
 use boa_engine::context::time::JsInstant;
@@ -25,7 +27,7 @@ use boa_engine::{
     vm::flowgraph::{Direction, Graph},
 };
 use boa_parser::source::ReadChar;
-use clap::{Parser, ValueEnum, ValueHint};
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
 use color_eyre::{
     Result, Section,
     eyre::{WrapErr, eyre},
@@ -43,7 +45,7 @@ use std::{
     cell::RefCell,
     collections::VecDeque,
     eprintln,
-    fs::OpenOptions,
+    fs::{self, OpenOptions},
     io,
     path::{Path, PathBuf},
     println,
@@ -89,6 +91,9 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 /// CLI configuration for Boa.
 static CLI_HISTORY: &str = ".boa_history";
 
+/// Maximum number of events kept in the timeline recorded with `--timeline`.
+const TIMELINE_CAPACITY: usize = 100_000;
+
 // Added #[allow(clippy::option_option)] because to StructOpt an Option<Option<T>>
 // is an optional argument that optionally takes a value ([--opt=[val]]).
 // https://docs.rs/structopt/0.3.11/structopt/#type-magic
@@ -101,7 +106,7 @@ struct Opt {
     files: Vec<PathBuf>,
 
     /// Run in strict mode.
-    #[arg(long)]
+    #[arg(long, global = true)]
     strict: bool,
 
     /// Dump the AST to stdout with the given format.
@@ -117,17 +122,26 @@ struct Opt {
     dump_ast: Option<Option<DumpFormat>>,
 
     /// Dump the AST to stdout with the given format.
-    #[arg(long, short, conflicts_with = "graph")]
+    #[arg(long, short, global = true)]
     trace: bool,
 
+    /// Count the executed opcodes and dump the statistics to stdout when exiting.
+    #[arg(long, global = true)]
+    vm_stats: bool,
+
+    /// Record a timeline of the engine events and write it to the given file when exiting,
+    /// in the Trace Event Format.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath, global = true)]
+    timeline: Option<PathBuf>,
+
     /// Use vi mode in the REPL
     #[arg(long = "vi")]
     vi_mode: bool,
 
-    #[arg(long, short = 'O', group = "optimizer")]
+    #[arg(long, short = 'O', group = "optimizer", global = true)]
     optimize: bool,
 
-    #[arg(long, requires = "optimizer")]
+    #[arg(long, requires = "optimizer", global = true)]
     optimizer_statistics: bool,
 
     /// Generate instruction flowgraph. Default is Graphviz.
@@ -136,7 +150,8 @@ struct Opt {
         value_name = "FORMAT",
         ignore_case = true,
         value_enum,
-        group = "graph"
+        group = "graph",
+        conflicts_with_all = ["trace", "vm_stats"]
     )]
     #[allow(clippy::option_option)]
     flowgraph: Option<Option<FlowgraphFormat>>,
@@ -152,21 +167,30 @@ struct Opt {
     flowgraph_direction: Option<FlowgraphDirection>,
 
     /// Inject debugging object `$boa`.
-    #[arg(long)]
+    #[arg(long, global = true)]
     debug_object: bool,
 
     /// Treats the input files as modules.
-    #[arg(long, short = 'm', group = "mod")]
+    #[arg(long, short = 'm', group = "mod", global = true)]
     module: bool,
 
     /// Root path from where the module resolver will try to load the modules.
-    #[arg(long, short = 'r', default_value_os_t = PathBuf::from("."), requires = "mod")]
+    #[arg(
+        long,
+        short = 'r',
+        default_value_os_t = PathBuf::from("."),
+        requires = "mod",
+        global = true
+    )]
     root: PathBuf,
 
     /// Execute a JavaScript expression then exit. Files (see above) will be
     /// executed prior to the expression.
     #[arg(long, short = 'e')]
     expression: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 impl Opt {
@@ -176,6 +200,15 @@ impl Opt {
     }
 }
 
+/// The subcommands of the CLI.
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run the given files with the sampling profiler enabled.
+    Profile(profile::ProfileArgs),
+    /// Run the given files with code coverage collection enabled.
+    Coverage(coverage::CoverageArgs),
+}
+
 /// The different types of format available for dumping.
 #[derive(Debug, Copy, Clone, Default, ValueEnum)]
 enum DumpFormat {
@@ -433,6 +466,14 @@ fn main() -> Result<()> {
     // Trace Output
     context.set_trace(args.trace);
 
+    // Opcode execution counters
+    context.set_vm_stats(args.vm_stats);
+
+    // Engine events timeline
+    if args.timeline.is_some() {
+        context.start_timeline(TIMELINE_CAPACITY);
+    }
+
     if args.debug_object {
         init_boa_debug_object(&mut context);
     }
@@ -443,17 +484,35 @@ fn main() -> Result<()> {
     optimizer_options.set(OptimizerOptions::OPTIMIZE_ALL, args.optimize);
     context.set_optimizer_options(optimizer_options);
 
-    if !args.files.is_empty() {
-        evaluate_files(&args, &mut context, &loader, &printer)?;
+    if let Some(ref command) = args.command {
+        if !args.files.is_empty() || args.expression.is_some() {
+            return Err(eyre!(
+                "files and expressions cannot be evaluated with a subcommand, \
+                 pass the files to the subcommand instead"
+            ));
+        }
+
+        let result = match command {
+            Command::Profile(profile_args) => {
+                profile::run(profile_args, &args, &mut context, &loader, &printer)
+            }
+            Command::Coverage(coverage_args) => {
+                coverage::run(coverage_args, &args, &mut context, &loader, &printer)
+            }
+        };
+        let dump = dump_instrumentation(&args, &mut context);
+        return result.and(dump);
+    }
 
-        if let Some(ref expr) = args.expression {
-            evaluate_expr(expr, &args, &mut context, &printer)?;
+    if !args.files.is_empty() || args.expression.is_some() {
+        let mut result = evaluate_files(&args, &mut context, &loader, &printer);
+        if let (Ok(()), Some(expr)) = (&result, &args.expression) {
+            result = evaluate_expr(expr, &args, &mut context, &printer);
         }
 
-        return Ok(());
-    } else if let Some(ref expr) = args.expression {
-        evaluate_expr(expr, &args, &mut context, &printer)?;
-        return Ok(());
+        // The instrumentation is also dumped when the evaluation fails.
+        let dump = dump_instrumentation(&args, &mut context);
+        return result.and(dump);
     }
 
     let handle = start_readline_thread(sender, printer.clone(), args.vi_mode);
@@ -475,6 +534,20 @@ fn main() -> Result<()> {
 
     handle.join().expect("failed to join thread");
 
+    dump_instrumentation(&args, &mut context)
+}
+
+/// Dumps the opcode execution counters to stdout and writes the timeline, if enabled.
+fn dump_instrumentation(args: &Opt, context: &mut Context) -> Result<()> {
+    if let Some(stats) = context.vm_stats() {
+        println!("{stats}");
+    }
+
+    if let (Some(path), Some(timeline)) = (&args.timeline, context.stop_timeline()) {
+        let json = serde_json::to_string(&timeline.to_trace_events())?;
+        fs::write(path, json).wrap_err_with(|| format!("could not write `{}`", path.display()))?;
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,291 @@
+//! Implementation of the `boa profile` subcommand.
+//!
+//! Runs the given files with the VM sampling profiler enabled, and outputs the result
+//! in the folded stack format or as a flamegraph SVG image.
+
+use crate::{Opt, evaluate_file, logger::SharedExternalPrinterLogger};
+use boa_engine::{Context, module::SimpleModuleLoader, vm::profiler::Profile};
+use clap::{Args, ValueHint};
+use color_eyre::{Result, eyre::WrapErr};
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf};
+
+/// Default number of executed instructions between two samples of the call stack.
+pub(crate) const DEFAULT_SAMPLE_INTERVAL: u32 = 100;
+
+/// Width of the generated flamegraph image, in pixels.
+const IMAGE_WIDTH: f64 = 1200.0;
+
+/// Height of a single frame of the flamegraph, in pixels.
+const FRAME_HEIGHT: f64 = 16.0;
+
+/// Vertical padding of the flamegraph, in pixels.
+const PADDING: f64 = 32.0;
+
+/// Approximate width of a character of the frame labels, in pixels.
+const CHAR_WIDTH: f64 = 7.0;
+
+#[derive(Debug, Args)]
+pub(crate) struct ProfileArgs {
+    /// The JavaScript file(s) to be profiled.
+    #[arg(name = "FILE", value_hint = ValueHint::FilePath, required = true)]
+    files: Vec<PathBuf>,
+
+    /// Write a flamegraph of the profile to the given SVG file.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    flamegraph: Option<PathBuf>,
+
+    /// Write the profile in the folded stack format to the given file.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    folded: Option<PathBuf>,
+
+    /// Number of executed instructions between two samples of the call stack.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_SAMPLE_INTERVAL)]
+    sample_interval: u32,
+}
+
+/// Runs the `profile` subcommand.
+///
+/// If no output file is given, the folded stacks are printed to stdout. The profile is also
+/// reported when a file throws, in which case the error is returned afterwards.
+pub(crate) fn run(
+    profile_args: &ProfileArgs,
+    args: &Opt,
+    context: &mut Context,
+    loader: &SimpleModuleLoader,
+    printer: &SharedExternalPrinterLogger,
+) -> Result<()> {
+    context.start_profiler(profile_args.sample_interval);
+    let result = profile_args
+        .files
+        .iter()
+        .try_for_each(|file| evaluate_file(file, args, context, loader, printer));
+    let profile = context.stop_profiler().unwrap_or_default();
+
+    if let Some(path) = &profile_args.folded {
+        fs::write(path, profile.to_folded())
+            .wrap_err_with(|| format!("could not write `{}`", path.display()))?;
+    }
+
+    if let Some(path) = &profile_args.flamegraph {
+        let title = profile_args
+            .files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        fs::write(path, render_flamegraph(&profile, &title))
+            .wrap_err_with(|| format!("could not write `{}`", path.display()))?;
+    }
+
+    if profile_args.folded.is_none() && profile_args.flamegraph.is_none() {
+        print!("{}", profile.to_folded());
+    }
+
+    result
+}
+
+/// A node of the merged call tree.
+#[derive(Debug, Default)]
+struct Node {
+    samples: u64,
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| child.depth() + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Renders the profile as a flamegraph SVG image, with the outermost frames at the bottom.
+fn render_flamegraph(profile: &Profile, title: &str) -> String {
+    let mut root = Node::default();
+    for stack in profile.stacks() {
+        root.samples += stack.samples();
+        let mut node = &mut root;
+        for frame in stack.frames() {
+            node = node.children.entry(frame.clone()).or_default();
+            node.samples += stack.samples();
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = root.depth() as f64 * FRAME_HEIGHT + PADDING * 2.0;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg version="1.1" width="{IMAGE_WIDTH}" height="{height}" viewBox="0 0 {IMAGE_WIDTH} {height}" xmlns="http://www.w3.org/2000/svg">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect x="0" y="0" width="100%" height="100%" fill="#f8f8f8"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" font-family="monospace" font-size="16" text-anchor="middle">{}</text>"#,
+        IMAGE_WIDTH / 2.0,
+        PADDING / 2.0 + 6.0,
+        escape(title)
+    );
+
+    if root.samples != 0 {
+        let mut renderer = Renderer {
+            svg,
+            #[allow(clippy::cast_precision_loss)]
+            scale: IMAGE_WIDTH / root.samples as f64,
+            height,
+            total: root.samples,
+        };
+        renderer.render_children(&root, 0.0, 0.0);
+        svg = renderer.svg;
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Renders the frames of a call tree into an SVG image.
+struct Renderer {
+    svg: String,
+    scale: f64,
+    height: f64,
+    total: u64,
+}
+
+impl Renderer {
+    /// Renders the children of `node`, side by side starting at `offset`.
+    fn render_children(&mut self, node: &Node, offset: f64, depth: f64) {
+        let mut offset = offset;
+        for (name, child) in &node.children {
+            self.render_node(name, child, offset, depth);
+            #[allow(clippy::cast_precision_loss)]
+            {
+                offset += child.samples as f64 * self.scale;
+            }
+        }
+    }
+
+    fn render_node(&mut self, name: &str, node: &Node, offset: f64, depth: f64) {
+        #[allow(clippy::cast_precision_loss)]
+        let width = node.samples as f64 * self.scale;
+
+        // Frames thinner than this are not visible anyway.
+        if width < 0.1 {
+            return;
+        }
+
+        let top = self.height - PADDING - (depth + 1.0) * FRAME_HEIGHT;
+        #[allow(clippy::cast_precision_loss)]
+        let percent = node.samples as f64 * 100.0 / self.total as f64;
+        let (red, green, blue) = color(name);
+
+        let _ = writeln!(
+            self.svg,
+            r#"<g><title>{} ({} samples, {percent:.2}%)</title><rect x="{offset:.2}" y="{top:.2}" width="{width:.2}" height="{}" fill="rgb({red},{green},{blue})" rx="2" ry="2"/>"#,
+            escape(name),
+            node.samples,
+            FRAME_HEIGHT - 1.0,
+        );
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let max_chars = ((width - 6.0) / CHAR_WIDTH) as usize;
+        if max_chars >= 3 {
+            let label = if name.chars().count() > max_chars {
+                let mut label = name.chars().take(max_chars - 2).collect::<String>();
+                label.push_str("..");
+                label
+            } else {
+                name.to_string()
+            };
+            let _ = write!(
+                self.svg,
+                r#"<text x="{:.2}" y="{:.2}" font-family="monospace" font-size="12">{}</text>"#,
+                offset + 3.0,
+                top + FRAME_HEIGHT - 4.5,
+                escape(&label)
+            );
+        }
+        self.svg.push_str("</g>\n");
+
+        self.render_children(node, offset, depth + 1.0);
+    }
+}
+
+/// Computes a stable color from the "hot" palette for the given frame name.
+fn color(name: &str) -> (u8, u8, u8) {
+    // FNV-1a, so colors are consistent between runs.
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    let [red, green, blue, ..] = hash.to_le_bytes();
+    (205 + red % 51, green % 231, blue % 56)
+}
+
+/// Escapes the characters that have a special meaning in XML.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, render_flamegraph};
+    use boa_engine::{Context, Source, vm::profiler::Profile};
+
+    #[test]
+    fn flamegraph() {
+        let context = &mut Context::default();
+        context.start_profiler(1);
+        context
+            .eval(Source::from_bytes(
+                "function hot() { let s = 0; for (let i = 0; i < 1000; i++) { s += i; } return s; }
+                hot();",
+            ))
+            .unwrap();
+        let profile = context.stop_profiler().unwrap();
+
+        let svg = render_flamegraph(&profile, "a&b.js");
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">a&amp;b.js</text>"));
+        assert!(svg.contains("<title>hot ("));
+
+        // Callees are drawn above their callers.
+        let y = |name: &str| -> f64 {
+            let frame = &svg[svg.find(&format!("<title>{name} (")).unwrap()..];
+            let y = &frame[frame.find(" y=\"").unwrap() + 4..];
+            y[..y.find('"').unwrap()].parse().unwrap()
+        };
+        assert!(y("hot") < y("&lt;main&gt;"));
+    }
+
+    #[test]
+    fn empty_flamegraph() {
+        let svg = render_flamegraph(&Profile::default(), "empty.js");
+        assert!(!svg.contains("<g>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!(
+            escape(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
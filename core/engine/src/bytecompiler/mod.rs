@@ -1634,7 +1634,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                         let index = self.insert_binding(binding);
                         let value = self.register_allocator.alloc();
                         self.emit_binding_access(BindingAccessOpcode::GetLocator, &index, &value);
-                        self.compile_expr(expr, &value);
+                        self.position_guard(expr).compile_expr(expr, &value);
                         self.emit_binding_access(
                             BindingAccessOpcode::SetNameByLocator,
                             &index,
@@ -1650,7 +1650,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                 Binding::Pattern(pattern) => {
                     let value = self.register_allocator.alloc();
                     if let Some(init) = variable.init() {
-                        self.compile_expr(init, &value);
+                        self.position_guard(init).compile_expr(init, &value);
                     } else {
                         self.bytecode.emit_push_undefined(value.variable());
                     }
@@ -1671,7 +1671,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                             let ident = ident.to_js_string(self.interner());
                             let value = self.register_allocator.alloc();
                             if let Some(init) = variable.init() {
-                                self.compile_expr(init, &value);
+                                self.position_guard(init).compile_expr(init, &value);
                             } else {
                                 self.bytecode.emit_push_undefined(value.variable());
                             }
@@ -1681,7 +1681,7 @@ impl<'ctx> ByteCompiler<'ctx> {
                         Binding::Pattern(pattern) => {
                             let value = self.register_allocator.alloc();
                             if let Some(init) = variable.init() {
-                                self.compile_expr(init, &value);
+                                self.position_guard(init).compile_expr(init, &value);
                             } else {
                                 self.bytecode.emit_push_undefined(value.variable());
                             }
@@ -1704,14 +1704,14 @@ impl<'ctx> ByteCompiler<'ctx> {
                                 .init()
                                 .expect("const declaration must have initializer");
                             let value = self.register_allocator.alloc();
-                            self.compile_expr(init, &value);
+                            self.position_guard(init).compile_expr(init, &value);
                             self.emit_binding(BindingOpcode::InitLexical, ident, &value);
                             self.register_allocator.dealloc(value);
                         }
                         Binding::Pattern(pattern) => {
                             let value = self.register_allocator.alloc();
                             if let Some(init) = variable.init() {
-                                self.compile_expr(init, &value);
+                                self.position_guard(init).compile_expr(init, &value);
                             } else {
                                 self.bytecode.emit_push_undefined(value.variable());
                             }
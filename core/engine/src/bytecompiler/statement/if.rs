@@ -4,7 +4,8 @@ use boa_ast::statement::If;
 impl ByteCompiler<'_> {
     pub(crate) fn compile_if(&mut self, node: &If, use_expr: bool) {
         let value = self.register_allocator.alloc();
-        self.compile_expr(node.cond(), &value);
+        self.position_guard(node.cond())
+            .compile_expr(node.cond(), &value);
         let jelse = self.jump_if_false(&value);
         self.register_allocator.dealloc(value);
         self.compile_stmt(node.body(), use_expr, true);
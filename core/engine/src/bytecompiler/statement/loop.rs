@@ -29,7 +29,7 @@ impl ByteCompiler<'_> {
             match init {
                 ForLoopInitializer::Expression(expr) => {
                     let value = self.register_allocator.alloc();
-                    self.compile_expr(expr, &value);
+                    self.position_guard(expr).compile_expr(expr, &value);
                     self.register_allocator.dealloc(value);
                 }
                 ForLoopInitializer::Var(decl) => {
@@ -121,7 +121,8 @@ impl ByteCompiler<'_> {
 
         if let Some(final_expr) = for_loop.final_expr() {
             let value = self.register_allocator.alloc();
-            self.compile_expr(final_expr, &value);
+            self.position_guard(final_expr)
+                .compile_expr(final_expr, &value);
             self.register_allocator.dealloc(value);
         }
 
@@ -129,7 +130,8 @@ impl ByteCompiler<'_> {
 
         let value = self.register_allocator.alloc();
         if let Some(condition) = for_loop.condition() {
-            self.compile_expr(condition, &value);
+            self.position_guard(condition)
+                .compile_expr(condition, &value);
         } else {
             self.bytecode.emit_push_true(value.variable());
         }
@@ -381,7 +383,8 @@ impl ByteCompiler<'_> {
         self.push_loop_control_info(label, start_address, use_expr);
 
         let value = self.register_allocator.alloc();
-        self.compile_expr(while_loop.condition(), &value);
+        self.position_guard(while_loop.condition())
+            .compile_expr(while_loop.condition(), &value);
         let exit = self.jump_if_false(&value);
         self.register_allocator.dealloc(value);
 
@@ -409,7 +412,8 @@ impl ByteCompiler<'_> {
         self.bytecode.emit_increment_loop_iteration();
 
         let value = self.register_allocator.alloc();
-        self.compile_expr(do_while_loop.cond(), &value);
+        self.position_guard(do_while_loop.cond())
+            .compile_expr(do_while_loop.cond(), &value);
         let exit = self.jump_if_false(&value);
         self.register_allocator.dealloc(value);
 
@@ -71,7 +71,7 @@ impl ByteCompiler<'_> {
             Statement::Return(ret) => {
                 let value = self.register_allocator.alloc();
                 if let Some(expr) = ret.target() {
-                    self.compile_expr(expr, &value);
+                    self.position_guard(expr).compile_expr(expr, &value);
 
                     if self.is_async_generator() {
                         self.bytecode.emit_await(value.variable());
@@ -92,12 +92,14 @@ impl ByteCompiler<'_> {
             }
             Statement::Try(t) => self.compile_try(t, use_expr),
             Statement::Expression(expr) => {
-                let value = self.register_allocator.alloc();
-                self.compile_expr(expr, &value);
+                let mut compiler = self.position_guard(expr);
+
+                let value = compiler.register_allocator.alloc();
+                compiler.compile_expr(expr, &value);
                 if use_expr {
-                    self.bytecode.emit_set_accumulator(value.variable());
+                    compiler.bytecode.emit_set_accumulator(value.variable());
                 }
-                self.register_allocator.dealloc(value);
+                compiler.register_allocator.dealloc(value);
             }
             Statement::With(with) => self.compile_with(with, use_expr),
             Statement::Empty | Statement::Debugger => {}
@@ -97,10 +97,6 @@ pub struct Context {
     /// Execute in strict mode,
     strict: bool,
 
-    /// Number of instructions remaining before a forced exit
-    #[cfg(feature = "fuzz")]
-    pub(crate) instructions_remaining: usize,
-
     pub(crate) vm: Vm,
 
     pub(crate) kept_alive: Vec<JsObject>,
@@ -460,6 +456,210 @@ impl Context {
         self.vm.trace = trace;
     }
 
+    /// Enables or disables the collection of opcode execution counters.
+    ///
+    /// Enabling the counters when they are already enabled keeps the collected statistics,
+    /// while disabling them discards the statistics.
+    #[cfg(feature = "vm-stats")]
+    #[inline]
+    pub fn set_vm_stats(&mut self, enabled: bool) {
+        if !enabled {
+            self.vm.stats = None;
+        } else if self.vm.stats.is_none() {
+            self.vm.stats = Some(crate::vm::stats::VmStats::default());
+        }
+    }
+
+    /// Returns the opcode execution counters collected so far, or `None` if the
+    /// collection is disabled.
+    #[cfg(feature = "vm-stats")]
+    #[inline]
+    #[must_use]
+    pub fn vm_stats(&self) -> Option<&crate::vm::stats::VmStats> {
+        self.vm.stats.as_ref()
+    }
+
+    /// Returns the opcode execution counters collected so far and resets them,
+    /// or `None` if the collection is disabled.
+    #[cfg(feature = "vm-stats")]
+    #[inline]
+    pub fn take_vm_stats(&mut self) -> Option<crate::vm::stats::VmStats> {
+        self.vm.stats.as_mut().map(std::mem::take)
+    }
+
+    /// Sets the number of instructions that can be executed before the instruction budget
+    /// handler is called, or removes the budget if `budget` is `None`.
+    ///
+    /// This allows to interrupt runaway scripts deterministically, independently of the
+    /// speed of the host. When the budget is exhausted, the handler set with
+    /// [`Context::set_instruction_budget_handler`] decides whether the execution continues with
+    /// a new budget. If there is no handler, or it does not return a new budget, the execution
+    /// is interrupted with an [`EngineError::InstructionBudgetExhausted`] error, which cannot be
+    /// caught by ECMAScript code.
+    ///
+    /// The budget is shared by all the executions on this context, so it must be set again
+    /// after it has been exhausted in order to run more code.
+    ///
+    /// This function is only available if the `interrupt` feature is enabled.
+    ///
+    /// [`EngineError::InstructionBudgetExhausted`]: crate::error::EngineError::InstructionBudgetExhausted
+    #[cfg(feature = "interrupt")]
+    #[inline]
+    pub fn set_instruction_budget(&mut self, budget: Option<u64>) {
+        self.vm.instruction_budget.set_remaining(budget);
+    }
+
+    /// Returns the number of instructions that can still be executed, or `None` if there is
+    /// no instruction budget.
+    #[cfg(feature = "interrupt")]
+    #[inline]
+    #[must_use]
+    pub fn instruction_budget(&self) -> Option<u64> {
+        self.vm.instruction_budget.remaining()
+    }
+
+    /// Sets the handler called when the instruction budget is exhausted.
+    ///
+    /// The handler returns the number of instructions the execution can continue with, or `None`
+    /// to interrupt the execution. See [`Context::set_instruction_budget`] for more information.
+    #[cfg(feature = "interrupt")]
+    #[inline]
+    pub fn set_instruction_budget_handler<F>(&mut self, handler: F)
+    where
+        F: FnMut(&mut Self) -> Option<u64> + 'static,
+    {
+        self.vm.instruction_budget.handler = Some(Box::new(handler));
+    }
+
+    /// Returns a handle to interrupt the execution of this context from other threads.
+    ///
+    /// All the handles returned by this method share the same state.
+    ///
+    /// This function is only available if the `interrupt` feature is enabled.
+    #[cfg(feature = "interrupt")]
+    #[inline]
+    #[must_use]
+    pub fn interrupt_handle(&self) -> crate::vm::InterruptHandle {
+        self.vm.interrupt.clone()
+    }
+
+    /// Starts the sampling profiler, discarding any profile currently being collected.
+    ///
+    /// The call stack is sampled every `sample_interval` executed instructions.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub fn start_profiler(&mut self, sample_interval: u32) {
+        self.vm.profiler = Some(crate::vm::profiler::Profiler::new(sample_interval));
+    }
+
+    /// Stops the sampling profiler, returning the collected profile or `None` if
+    /// the profiler was not running.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    pub fn stop_profiler(&mut self) -> Option<crate::vm::profiler::Profile> {
+        self.vm
+            .profiler
+            .take()
+            .map(crate::vm::profiler::Profiler::finish)
+    }
+
+    /// Returns `true` if the sampling profiler is running.
+    #[cfg(feature = "profiler")]
+    #[inline]
+    #[must_use]
+    pub fn is_profiling(&self) -> bool {
+        self.vm.profiler.is_some()
+    }
+
+    /// Starts collecting code coverage, discarding any coverage collected so far.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn start_coverage(&mut self) {
+        self.vm.coverage = Some(crate::vm::coverage::CoverageCollector::default());
+    }
+
+    /// Returns the code coverage collected since the collection was started or last taken,
+    /// and resets the counters, or `None` if the collection is not running.
+    ///
+    /// The collection keeps running after taking the coverage.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn take_coverage(&mut self) -> Option<crate::vm::coverage::Coverage> {
+        self.vm
+            .coverage
+            .as_mut()
+            .map(crate::vm::coverage::CoverageCollector::take)
+    }
+
+    /// Stops collecting code coverage, returning the coverage collected since the collection
+    /// was started or last taken, or `None` if the collection was not running.
+    #[cfg(feature = "coverage")]
+    #[inline]
+    pub fn stop_coverage(&mut self) -> Option<crate::vm::coverage::Coverage> {
+        self.vm
+            .coverage
+            .take()
+            .map(|mut collector| collector.take())
+    }
+
+    /// Starts recording a timeline of the engine events, keeping at most the last `capacity`
+    /// events. Any timeline recorded so far is discarded.
+    #[cfg(feature = "timeline")]
+    #[inline]
+    pub fn start_timeline(&mut self, capacity: usize) {
+        self.vm.timeline = Some(crate::vm::timeline::TimelineRecorder::new(capacity));
+    }
+
+    /// Stops recording the timeline, returning the recorded events, or `None` if the timeline
+    /// was not being recorded.
+    #[cfg(feature = "timeline")]
+    #[inline]
+    pub fn stop_timeline(&mut self) -> Option<crate::vm::timeline::Timeline> {
+        self.vm
+            .timeline
+            .take()
+            .map(crate::vm::timeline::TimelineRecorder::finish)
+    }
+
+    /// Returns `true` if the timeline is being recorded.
+    #[cfg(feature = "timeline")]
+    #[inline]
+    #[must_use]
+    pub const fn is_recording_timeline(&self) -> bool {
+        self.vm.timeline.is_some()
+    }
+
+    /// Adds a mark with the given name to the timeline, if it is being recorded.
+    #[cfg(feature = "timeline")]
+    #[inline]
+    pub fn timeline_mark(&mut self, name: &str) {
+        if let Some(timeline) = &mut self.vm.timeline {
+            timeline.mark(name.to_string());
+        }
+    }
+
+    /// Returns the current time of the timeline, if it is being recorded.
+    #[cfg(feature = "timeline")]
+    pub(crate) fn timeline_now(&self) -> Option<std::time::Duration> {
+        self.vm
+            .timeline
+            .as_ref()
+            .map(crate::vm::timeline::TimelineRecorder::now)
+    }
+
+    /// Records a timeline event that started at `start`, as returned by [`Self::timeline_now`].
+    #[cfg(feature = "timeline")]
+    pub(crate) fn timeline_record(
+        &mut self,
+        start: Option<std::time::Duration>,
+        category: crate::vm::timeline::TimelineCategory,
+        name: impl FnOnce() -> String,
+    ) {
+        if let (Some(timeline), Some(start)) = (&mut self.vm.timeline, start) {
+            timeline.record(category, name(), start);
+        }
+    }
+
     /// Get optimizer options.
     #[inline]
     #[must_use]
@@ -919,7 +1119,7 @@ pub struct ContextBuilder {
     #[cfg(feature = "temporal")]
     timezone_provider: Option<Box<dyn TimeZoneProvider>>,
     #[cfg(feature = "fuzz")]
-    instructions_remaining: usize,
+    instructions_remaining: Option<u64>,
 }
 
 impl std::fmt::Debug for ContextBuilder {
@@ -1082,11 +1282,13 @@ impl ContextBuilder {
 
     /// Specifies the number of instructions remaining to the [`Context`].
     ///
+    /// This is a shorthand for [`Context::set_instruction_budget`] on the built context.
+    ///
     /// This function is only available if the `fuzz` feature is enabled.
     #[cfg(feature = "fuzz")]
     #[must_use]
-    pub const fn instructions_remaining(mut self, instructions_remaining: usize) -> Self {
-        self.instructions_remaining = instructions_remaining;
+    pub const fn instructions_remaining(mut self, instructions_remaining: u64) -> Self {
+        self.instructions_remaining = Some(instructions_remaining);
         self
     }
 
@@ -1151,8 +1353,6 @@ impl ContextBuilder {
                     }
                 }
             },
-            #[cfg(feature = "fuzz")]
-            instructions_remaining: self.instructions_remaining,
             kept_alive: Vec::new(),
             host_hooks,
             clock,
@@ -1167,6 +1367,9 @@ impl ContextBuilder {
 
         builtins::set_default_global_bindings(&mut context)?;
 
+        #[cfg(feature = "fuzz")]
+        context.set_instruction_budget(self.instructions_remaining);
+
         Ok(context)
     }
 }
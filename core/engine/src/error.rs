@@ -11,10 +11,11 @@ use crate::{
     property::PropertyDescriptor,
     realm::Realm,
     vm::{
-        NativeSourceInfo,
+        NativeSourceInfo, SourcePath,
         shadow_stack::{Backtrace, ShadowEntry},
     },
 };
+use boa_ast::Position;
 use boa_gc::{Finalize, Trace, custom_trace};
 use std::{
     borrow::Cow,
@@ -322,15 +323,24 @@ pub enum RuntimeLimitError {
 /// Engine error that cannot be caught from within ECMAScript code.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Error, Trace, Finalize)]
 #[boa_gc(empty_trace)]
+#[non_exhaustive]
 pub enum EngineError {
-    /// Error thrown when no instructions remain. Only used in a fuzzing context.
-    #[cfg(feature = "fuzz")]
-    #[error("NoInstructionsRemainError: instruction budget was exhausted")]
-    NoInstructionsRemain,
-
     /// Error thrown when a runtime limit is exceeded.
     #[error("RuntimeLimitError: {0}")]
     RuntimeLimit(#[from] RuntimeLimitError),
+
+    /// Error thrown when the instruction budget of the context is exhausted.
+    ///
+    /// See [`Context::set_instruction_budget`](crate::Context::set_instruction_budget).
+    #[cfg(feature = "interrupt")]
+    #[error("InstructionBudgetError: the instruction budget was exhausted")]
+    InstructionBudgetExhausted,
+
+    /// Error thrown when the execution is terminated through an
+    /// [`InterruptHandle`](crate::vm::InterruptHandle).
+    #[cfg(feature = "interrupt")]
+    #[error("TerminationError: the execution was terminated")]
+    Terminated,
 }
 
 impl JsError {
@@ -623,6 +633,19 @@ impl JsError {
         }
     }
 
+    /// Returns the frames of the call stack at the point where the error was thrown, starting
+    /// from the innermost frame, or `None` if the error was not thrown while running ECMAScript
+    /// code.
+    ///
+    /// The number of frames is limited by the [backtrace limit] of the context.
+    ///
+    /// [backtrace limit]: crate::vm::RuntimeLimits::backtrace_limit
+    #[must_use]
+    pub fn backtrace(&self) -> Option<Vec<BacktraceFrame>> {
+        let backtrace = self.backtrace.as_ref()?;
+        Some(backtrace.iter().rev().map(BacktraceFrame::new).collect())
+    }
+
     /// Converts this error into its thread-safe, erased version.
     ///
     /// Even though this operation is lossy, converting into a `JsErasedError`
@@ -818,6 +841,63 @@ impl fmt::Display for JsError {
     }
 }
 
+/// A frame of the backtrace of a [`JsError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BacktraceFrame {
+    function_name: Option<JsString>,
+    path: SourcePath,
+    position: Option<Position>,
+    native: bool,
+}
+
+impl BacktraceFrame {
+    fn new(entry: &ShadowEntry) -> Self {
+        match entry {
+            ShadowEntry::Native { function_name, .. } => Self {
+                function_name: function_name.clone().filter(|name| !name.is_empty()),
+                path: SourcePath::None,
+                position: None,
+                native: true,
+            },
+            ShadowEntry::Bytecode { pc, source_info } => {
+                let function_name = source_info.function_name();
+                Self {
+                    function_name: (!function_name.is_empty()).then(|| function_name.clone()),
+                    path: source_info.map().path().clone(),
+                    position: source_info.map().find(*pc),
+                    native: false,
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the function of the frame, or `None` if the function is anonymous.
+    #[must_use]
+    pub const fn function_name(&self) -> Option<&JsString> {
+        self.function_name.as_ref()
+    }
+
+    /// Returns the source path of the frame.
+    ///
+    /// This is always [`SourcePath::None`] for native frames.
+    #[must_use]
+    pub const fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// Returns the position in the source that was executing in the frame, if known.
+    #[must_use]
+    pub const fn position(&self) -> Option<Position> {
+        self.position
+    }
+
+    /// Returns `true` if the frame is a call to a native function.
+    #[must_use]
+    pub const fn is_native(&self) -> bool {
+        self.native
+    }
+}
+
 /// Helper struct that ignores equality operator.
 #[derive(Debug, Clone, Finalize)]
 pub(crate) struct IgnoreEq<T>(pub(crate) T);
@@ -103,10 +103,13 @@ impl NativeJob {
     /// If the native job has an execution realm defined, this sets the running execution
     /// context to the realm's before calling the inner closure, and resets it after execution.
     pub fn call(self, context: &mut Context) -> JsResult<JsValue> {
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         // If realm is not null, each time job is invoked the implementation must perform
         // implementation-defined steps such that execution is prepared to evaluate ECMAScript
         // code at the time of job's invocation.
-        if let Some(realm) = self.realm {
+        let result = if let Some(realm) = self.realm {
             let old_realm = context.enter_realm(realm);
 
             // Let scriptOrModule be GetActiveScriptOrModule() at the time HostEnqueuePromiseJob is
@@ -120,7 +123,16 @@ impl NativeJob {
             result
         } else {
             (self.f)(context)
-        }
+        };
+
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Job,
+            || "job".to_string(),
+        );
+
+        result
     }
 }
 
@@ -680,7 +692,11 @@ impl JobExecutor for SimpleJobExecutor {
         Self: Sized,
     {
         let mut group = FutureGroup::new();
-        loop {
+        'run: loop {
+            if job_stop_requested(context) {
+                break;
+            }
+
             for job in mem::take(&mut *self.async_jobs.borrow_mut()) {
                 group.insert(job.call(context));
             }
@@ -710,10 +726,16 @@ impl JobExecutor for SimpleJobExecutor {
                 let mut timeouts_borrow = self.timeout_jobs.borrow_mut();
                 let mut jobs_to_keep = timeouts_borrow.split_off(&now);
                 jobs_to_keep.retain(|_, job| !job.is_cancelled());
-                let jobs_to_run = mem::replace(&mut *timeouts_borrow, jobs_to_keep);
+                let mut jobs_to_run = mem::replace(&mut *timeouts_borrow, jobs_to_keep);
                 drop(timeouts_borrow);
 
-                for job in jobs_to_run.into_values() {
+                while let Some((time, job)) = jobs_to_run.pop_first() {
+                    if job_stop_requested(context) {
+                        jobs_to_run.insert(time, job);
+                        self.timeout_jobs.borrow_mut().append(&mut jobs_to_run);
+                        break 'run;
+                    }
+
                     if let Err(err) = job.call(&mut context.borrow_mut()) {
                         self.clear();
                         return Err(err);
@@ -721,16 +743,28 @@ impl JobExecutor for SimpleJobExecutor {
                 }
             }
 
-            let jobs = mem::take(&mut *self.promise_jobs.borrow_mut());
-            for job in jobs {
+            let mut jobs = mem::take(&mut *self.promise_jobs.borrow_mut());
+            while let Some(job) = jobs.pop_front() {
+                if job_stop_requested(context) {
+                    jobs.push_front(job);
+                    requeue(&self.promise_jobs, jobs);
+                    break 'run;
+                }
+
                 if let Err(err) = job.call(&mut context.borrow_mut()) {
                     self.clear();
                     return Err(err);
                 }
             }
 
-            let jobs = mem::take(&mut *self.generic_jobs.borrow_mut());
-            for job in jobs {
+            let mut jobs = mem::take(&mut *self.generic_jobs.borrow_mut());
+            while let Some(job) = jobs.pop_front() {
+                if job_stop_requested(context) {
+                    jobs.push_front(job);
+                    requeue(&self.generic_jobs, jobs);
+                    break 'run;
+                }
+
                 if let Err(err) = job.call(&mut context.borrow_mut()) {
                     self.clear();
                     return Err(err);
@@ -740,6 +774,34 @@ impl JobExecutor for SimpleJobExecutor {
             future::yield_now().await;
         }
 
+        // NOTE: The async jobs that were already started cannot be put back in the queue, so
+        //       they are run to completion even if a job stop was requested.
+        while let Some(result) = group.next().await {
+            if let Err(err) = result {
+                self.clear();
+                return Err(err);
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Returns `true` if a job stop was requested through the
+/// [`InterruptHandle`](crate::vm::InterruptHandle) of the context, clearing the request.
+#[cfg(feature = "interrupt")]
+fn job_stop_requested(context: &RefCell<&mut Context>) -> bool {
+    context.borrow().vm.interrupt.take_job_stop_request()
+}
+
+#[cfg(not(feature = "interrupt"))]
+const fn job_stop_requested(_context: &RefCell<&mut Context>) -> bool {
+    false
+}
+
+/// Puts `jobs`, which were not started, back at the front of `queue`.
+fn requeue<T>(queue: &RefCell<VecDeque<T>>, mut jobs: VecDeque<T>) {
+    let mut queue = queue.borrow_mut();
+    jobs.append(&mut queue);
+    *queue = jobs;
+}
@@ -269,6 +269,9 @@ impl Module {
         realm: Option<Realm>,
         context: &mut Context,
     ) -> JsResult<Self> {
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let path = src.path().map(Path::to_path_buf);
         let realm = realm.unwrap_or_else(|| context.realm().clone());
 
@@ -280,6 +283,13 @@ impl Module {
         let source_text = SourceText::new(source);
         let src = SourceTextModule::new(module, context.interner(), source_text, path.clone());
 
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Parse,
+            || timeline_name(path.as_deref()),
+        );
+
         Ok(Self {
             inner: Gc::new(ModuleRepr {
                 realm,
@@ -794,6 +804,12 @@ impl<T: IntoIterator<Item = (JsString, NativeFunction)> + Clone> IntoJsModule fo
     }
 }
 
+/// Returns the name of the timeline events of a module.
+#[cfg(feature = "timeline")]
+fn timeline_name(path: Option<&Path>) -> String {
+    path.map_or_else(|| "<module>".to_string(), |path| path.display().to_string())
+}
+
 #[test]
 #[allow(clippy::missing_panics_doc)]
 fn into_js_module() {
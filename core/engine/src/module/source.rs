@@ -1564,6 +1564,9 @@ impl SourceTextModule {
         let global_env = realm.environment().clone();
         let env = self.code.source.scope().clone();
 
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let spanned_source_text = SpannedSourceText::new_source_only(self.code.source_text.clone());
         let mut compiler = ByteCompiler::new(
             js_string!("<main>"),
@@ -1747,6 +1750,13 @@ impl SourceTextModule {
             (Gc::new(compiler.finish()), functions)
         };
 
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Compile,
+            || super::timeline_name(module_self.path()),
+        );
+
         // 8. Let moduleContext be a new ECMAScript code execution context.
         let mut envs = EnvironmentStack::new(global_env);
         envs.push_module(self.code.source.scope().clone());
@@ -1908,10 +1918,20 @@ impl SourceTextModule {
         // 10. Else,
         //    a. Assert: capability is a PromiseCapability Record.
         //    b. Perform AsyncBlockStart(capability, module.[[ECMAScriptCode]], moduleContext).
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let result = context.run();
 
         context.vm.pop_frame();
 
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Evaluate,
+            || super::timeline_name(module_self.path()),
+        );
+
         //     f. If result is an abrupt completion, then
         if let CompletionRecord::Throw(err) = result {
             //    i. Return ? result.
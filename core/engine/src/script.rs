@@ -84,6 +84,9 @@ impl Script {
         realm: Option<Realm>,
         context: &mut Context,
     ) -> JsResult<Self> {
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let path = src.path().map(Path::to_path_buf);
         let mut parser = Parser::new(src);
         parser.set_identifier(context.next_parser_identifier());
@@ -98,6 +101,13 @@ impl Script {
 
         let source_text = SourceText::new(source);
 
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Parse,
+            || timeline_name(path.as_deref()),
+        );
+
         Ok(Self {
             inner: Gc::new(Inner {
                 realm: realm.unwrap_or_else(|| context.realm().clone()),
@@ -121,6 +131,9 @@ impl Script {
             return Ok(codeblock.clone());
         }
 
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let mut annex_b_function_names = Vec::new();
 
         global_declaration_instantiation_context(
@@ -158,6 +171,13 @@ impl Script {
 
         *codeblock = Some(cb.clone());
 
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Compile,
+            || timeline_name(self.path()),
+        );
+
         Ok(cb)
     }
 
@@ -169,9 +189,21 @@ impl Script {
     /// [`JobExecutor::run_jobs`]: crate::job::JobExecutor::run_jobs
     pub fn evaluate(&self, context: &mut Context) -> JsResult<JsValue> {
         self.prepare_run(context)?;
+
+        #[cfg(feature = "timeline")]
+        let timeline_start = context.timeline_now();
+
         let record = context.run();
 
         context.vm.pop_frame();
+
+        #[cfg(feature = "timeline")]
+        context.timeline_record(
+            timeline_start,
+            crate::vm::timeline::TimelineCategory::Evaluate,
+            || timeline_name(self.path()),
+        );
+
         record.consume()
     }
 
@@ -239,3 +271,9 @@ impl Script {
         self.inner.source_text.clone()
     }
 }
+
+/// Returns the name of the timeline events of a script.
+#[cfg(feature = "timeline")]
+fn timeline_name(path: Option<&Path>) -> String {
+    path.map_or_else(|| "<script>".to_string(), |path| path.display().to_string())
+}
@@ -0,0 +1,342 @@
+//! Code coverage collection for the virtual machine.
+//!
+//! When enabled with [`Context::start_coverage`], the VM counts how many times each instruction
+//! has been executed. The counters are then aggregated per function and per basic block when
+//! the coverage is taken with [`Context::take_coverage`], producing source ranges with their
+//! execution counts.
+//!
+//! A basic block is a sequence of instructions that is always entered by its first instruction
+//! and can only branch out at its last instruction, so the execution count of a block is the
+//! execution count of its first instruction.
+//!
+//! [`Context::start_coverage`]: crate::Context::start_coverage
+//! [`Context::take_coverage`]: crate::Context::take_coverage
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+use boa_ast::{Position, Span};
+use boa_gc::Gc;
+use boa_macros::js_str;
+use boa_string::JsString;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::{CodeBlock, Constant, Handler, Instruction, InstructionIterator, SourcePath};
+
+/// The per-instruction counters of the executed code blocks.
+#[derive(Debug, Default)]
+pub(crate) struct CoverageCollector {
+    code_blocks: FxHashMap<usize, CodeBlockCounters>,
+}
+
+#[derive(Debug)]
+struct CodeBlockCounters {
+    code_block: Gc<CodeBlock>,
+    counts: Box<[u64]>,
+}
+
+impl CoverageCollector {
+    /// Records the execution of the instruction at `pc` inside of `code_block`.
+    #[inline]
+    pub(crate) fn record(&mut self, code_block: &Gc<CodeBlock>, pc: u32) {
+        // NOTE: The code block is kept alive by the stored `Gc`, so its address
+        //       cannot be reused by another code block while it is in the map.
+        let key = std::ptr::from_ref::<CodeBlock>(code_block).addr();
+        let counters = self
+            .code_blocks
+            .entry(key)
+            .or_insert_with(|| CodeBlockCounters {
+                code_block: code_block.clone(),
+                counts: vec![0; code_block.bytecode.bytecode.len()].into_boxed_slice(),
+            });
+
+        if let Some(count) = counters.counts.get_mut(pc as usize) {
+            *count += 1;
+        }
+    }
+
+    /// Aggregates the counters collected so far into a [`Coverage`], and resets them.
+    ///
+    /// Functions that are declared inside of an executed code block but were never called
+    /// are included with a count of zero.
+    pub(crate) fn take(&mut self) -> Coverage {
+        let collected = std::mem::take(&mut self.code_blocks);
+
+        let mut pending = collected
+            .values()
+            .map(|counters| counters.code_block.clone())
+            .collect::<Vec<_>>();
+        let mut seen = FxHashSet::default();
+        let mut functions = Vec::new();
+
+        while let Some(code_block) = pending.pop() {
+            let key = std::ptr::from_ref::<CodeBlock>(&code_block).addr();
+            if !seen.insert(key) {
+                continue;
+            }
+
+            for constant in &code_block.constants {
+                if let Constant::Function(function) = constant {
+                    pending.push(function.clone());
+                }
+            }
+
+            let counts = collected.get(&key).map(|counters| &*counters.counts);
+            functions.push(FunctionCoverage::new(&code_block, counts));
+        }
+
+        functions.sort_by(|a, b| {
+            a.path
+                .to_string()
+                .cmp(&b.path.to_string())
+                .then(a.range.map(Span::start).cmp(&b.range.map(Span::start)))
+        });
+
+        Coverage { functions }
+    }
+}
+
+/// The coverage of all the functions that were loaded while collecting.
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    functions: Vec<FunctionCoverage>,
+}
+
+impl Coverage {
+    /// Returns the coverage of every function, sorted by source path and position.
+    #[must_use]
+    pub fn functions(&self) -> &[FunctionCoverage] {
+        &self.functions
+    }
+}
+
+/// The coverage of a single function, script or module.
+#[derive(Debug, Clone)]
+pub struct FunctionCoverage {
+    name: JsString,
+    path: SourcePath,
+    range: Option<Span>,
+    count: u64,
+    top_level: bool,
+    blocks: Vec<BlockCoverage>,
+    lines: Vec<(u32, u64)>,
+}
+
+impl FunctionCoverage {
+    fn new(code_block: &CodeBlock, counts: Option<&[u64]>) -> Self {
+        let count_at = |pc: u32| {
+            counts
+                .and_then(|counts| counts.get(pc as usize))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let entries = opening_entries(code_block);
+        let mut lines = BTreeMap::<u32, u64>::new();
+        let mut blocks = Vec::new();
+        for block in basic_blocks(code_block) {
+            let count = count_at(block.start);
+            let block_entries = entries_in(&entries, block);
+            for (_, position) in block_entries {
+                let line = lines.entry(position.line_number()).or_default();
+                *line = (*line).max(count);
+            }
+
+            blocks.push(BlockCoverage {
+                range: source_range(block_entries),
+                count,
+            });
+        }
+
+        let range = source_range(&entries);
+
+        Self {
+            name: code_block.name().clone(),
+            path: code_block.path().clone(),
+            range,
+            count: count_at(0),
+            top_level: code_block.name() == &js_str!("<main>"),
+            blocks,
+            lines: lines.into_iter().collect(),
+        }
+    }
+
+    /// Returns the name of the function.
+    #[must_use]
+    pub const fn name(&self) -> &JsString {
+        &self.name
+    }
+
+    /// Returns the source path of the function.
+    #[must_use]
+    pub const fn path(&self) -> &SourcePath {
+        &self.path
+    }
+
+    /// Returns the source range of the function, if it has source positions.
+    ///
+    /// The range goes from the first to the last source position of the instructions of the
+    /// function. Both are start positions of expressions or statements, so the range ends
+    /// where the last one starts, not at the end of the function.
+    #[must_use]
+    pub const fn range(&self) -> Option<Span> {
+        self.range
+    }
+
+    /// Returns the number of times the function was entered.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns `true` if this is the top level code of a script or module, rather than a
+    /// function.
+    #[must_use]
+    pub const fn is_top_level(&self) -> bool {
+        self.top_level
+    }
+
+    /// Returns the coverage of the basic blocks of the function, in bytecode order.
+    #[must_use]
+    pub fn blocks(&self) -> &[BlockCoverage] {
+        &self.blocks
+    }
+
+    /// Returns the line numbers that have instructions in the function, with the number of
+    /// times each line was executed, sorted by line number.
+    #[must_use]
+    pub fn lines(&self) -> &[(u32, u64)] {
+        &self.lines
+    }
+}
+
+/// The coverage of a basic block.
+#[derive(Debug, Clone, Copy)]
+pub struct BlockCoverage {
+    range: Option<Span>,
+    count: u64,
+}
+
+impl BlockCoverage {
+    /// Returns the source range of the block, if it has source positions.
+    ///
+    /// Like [`FunctionCoverage::range`], the range goes from the first to the last start
+    /// position of the instructions of the block, so it is empty if the block only has one.
+    #[must_use]
+    pub const fn range(&self) -> Option<Span> {
+        self.range
+    }
+
+    /// Returns the number of times the block was executed.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Splits the bytecode of `code_block` into basic blocks.
+fn basic_blocks(code_block: &CodeBlock) -> Vec<Range<u32>> {
+    #[allow(clippy::cast_possible_truncation)]
+    let end = code_block.bytecode.bytecode.len() as u32;
+
+    let mut leaders = BTreeSet::from([0]);
+    leaders.extend(code_block.handlers.iter().map(Handler::handler));
+
+    let mut iterator = InstructionIterator::new(&code_block.bytecode);
+    while let Some((_, _, instruction)) = iterator.next() {
+        #[allow(clippy::cast_possible_truncation)]
+        let next = iterator.pc() as u32;
+
+        match instruction {
+            Instruction::Jump { address }
+            | Instruction::JumpIfTrue { address, .. }
+            | Instruction::JumpIfFalse { address, .. }
+            | Instruction::JumpIfNotUndefined { address, .. }
+            | Instruction::JumpIfNullOrUndefined { address, .. }
+            | Instruction::JumpIfNotResumeKind { address, .. }
+            | Instruction::LogicalAnd { address, .. }
+            | Instruction::LogicalOr { address, .. }
+            | Instruction::Coalesce { address, .. }
+            | Instruction::Case { address, .. }
+            | Instruction::TemplateLookup { address, .. } => {
+                leaders.extend([address, next]);
+            }
+            Instruction::JumpTable {
+                default, addresses, ..
+            } => {
+                leaders.extend(addresses);
+                leaders.extend([default, next]);
+            }
+            Instruction::GeneratorDelegateNext {
+                throw_method_undefined,
+                return_method_undefined,
+                ..
+            } => {
+                leaders.extend([throw_method_undefined, return_method_undefined, next]);
+            }
+            Instruction::GeneratorDelegateResume { r#return, exit, .. } => {
+                leaders.extend([r#return, exit, next]);
+            }
+            Instruction::Return
+            | Instruction::Throw { .. }
+            | Instruction::ReThrow
+            | Instruction::ThrowNewTypeError { .. }
+            | Instruction::ThrowNewSyntaxError { .. }
+            | Instruction::ThrowNewReferenceError { .. }
+            | Instruction::GeneratorYield { .. }
+            | Instruction::AsyncGeneratorYield { .. }
+            | Instruction::Await { .. } => {
+                leaders.insert(next);
+            }
+            _ => {}
+        }
+    }
+
+    let leaders = leaders
+        .into_iter()
+        .filter(|leader| *leader < end)
+        .collect::<Vec<_>>();
+
+    leaders
+        .iter()
+        .enumerate()
+        .map(|(i, start)| *start..leaders.get(i + 1).copied().unwrap_or(end))
+        .collect()
+}
+
+/// Returns the source map entries of `code_block` that start a new source position.
+///
+/// When a nested position ends, the source map restores the position of the enclosing code,
+/// repeating an earlier entry. Counting those entries would credit loop updates and jumps to
+/// the line where the enclosing function or statement starts.
+fn opening_entries(code_block: &CodeBlock) -> Vec<(u32, Position)> {
+    let mut seen = FxHashSet::default();
+    code_block
+        .source_info()
+        .map()
+        .entries()
+        .iter()
+        .filter_map(|entry| Some((entry.pc(), entry.position()?)))
+        .filter(|(_, position)| seen.insert(*position))
+        .collect()
+}
+
+/// Returns the `entries` that start in `pcs`.
+///
+/// The source map entries are sorted by pc, so they can be found with a binary search.
+fn entries_in(entries: &[(u32, Position)], pcs: Range<u32>) -> &[(u32, Position)] {
+    let start = entries.partition_point(|(pc, _)| *pc < pcs.start);
+    let end = entries.partition_point(|(pc, _)| *pc < pcs.end);
+    &entries[start..end]
+}
+
+/// Returns the source range covered by the start positions of `entries`.
+fn source_range(entries: &[(u32, Position)]) -> Option<Span> {
+    let positions = entries.iter().map(|(_, position)| *position);
+
+    let start = positions.clone().min()?;
+    let end = positions.max()?;
+    Some(Span::new(start, end))
+}
@@ -0,0 +1,113 @@
+use std::fmt;
+
+use crate::{Context, JsResult, error::EngineError};
+
+/// The maximum number of instructions executed between two checks of the interrupt requests.
+const CHECK_INTERVAL: u32 = 1 << 12;
+
+/// The handler called when the instruction budget of a [`Context`] is exhausted.
+///
+/// Returns the number of instructions to continue with, or `None` to interrupt the execution.
+pub(crate) type InstructionBudgetHandler = dyn FnMut(&mut Context) -> Option<u64>;
+
+/// The number of instructions that can still be executed, see
+/// [`Context::set_instruction_budget`].
+///
+/// The VM only decrements a countdown for every instruction. When it reaches zero, the budget
+/// is updated and the requests of the [`InterruptHandle`](super::InterruptHandle) are checked,
+/// so there is a single check in the dispatch loop for both.
+#[derive(Default)]
+pub(crate) struct InstructionBudget {
+    /// The number of instructions until the next check.
+    pub(crate) countdown: u32,
+
+    /// The number of instructions between the last check and the next one.
+    window: u32,
+
+    /// The remaining number of instructions at the last check, or `None` if there is no budget.
+    remaining: Option<u64>,
+
+    /// The handler called when the budget is exhausted.
+    pub(crate) handler: Option<Box<InstructionBudgetHandler>>,
+}
+
+impl fmt::Debug for InstructionBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstructionBudget")
+            .field("countdown", &self.countdown)
+            .field("window", &self.window)
+            .field("remaining", &self.remaining)
+            .field(
+                "handler",
+                &self.handler.as_ref().map(|_| "InstructionBudgetHandler"),
+            )
+            .finish()
+    }
+}
+
+impl InstructionBudget {
+    /// Returns the number of instructions that can still be executed.
+    pub(crate) fn remaining(&self) -> Option<u64> {
+        let executed = u64::from(self.window - self.countdown);
+        self.remaining.map(|remaining| remaining - executed)
+    }
+
+    /// Sets the number of instructions that can still be executed, forcing a check before the
+    /// next instruction.
+    pub(crate) fn set_remaining(&mut self, remaining: Option<u64>) {
+        self.remaining = remaining;
+        self.window = 0;
+        self.countdown = 0;
+    }
+
+    /// Starts a new window of instructions until the next check.
+    fn start_window(&mut self) {
+        #[allow(clippy::cast_possible_truncation)]
+        let window = self.remaining.map_or(CHECK_INTERVAL, |remaining| {
+            remaining.min(u64::from(CHECK_INTERVAL)) as u32
+        });
+        self.window = window;
+        self.countdown = window;
+    }
+}
+
+impl Context {
+    /// Checks the interrupt requests and the instruction budget, once the countdown of the
+    /// instruction budget has reached zero.
+    ///
+    /// Returns an error if the execution must be interrupted.
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn check_instruction_budget(&mut self) -> JsResult<()> {
+        let remaining = self.vm.instruction_budget.remaining();
+        self.vm.instruction_budget.set_remaining(remaining);
+
+        if self.vm.interrupt.take_termination_request() {
+            return Err(EngineError::Terminated.into());
+        }
+
+        if remaining == Some(0) {
+            let budget = self
+                .refill_instruction_budget()
+                .ok_or(EngineError::InstructionBudgetExhausted)?;
+            self.vm.instruction_budget.set_remaining(Some(budget));
+        }
+
+        self.vm.instruction_budget.start_window();
+        Ok(())
+    }
+
+    /// Calls the handler of the exhausted instruction budget, returning the new budget,
+    /// or `None` if the execution must be interrupted.
+    fn refill_instruction_budget(&mut self) -> Option<u64> {
+        let mut handler = self.vm.instruction_budget.handler.take()?;
+        let budget = handler(self);
+
+        // NOTE: The handler may have replaced itself while running.
+        if self.vm.instruction_budget.handler.is_none() {
+            self.vm.instruction_budget.handler = Some(handler);
+        }
+
+        budget.filter(|budget| *budget != 0)
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU8, Ordering},
+};
+
+/// The execution must be terminated.
+const TERMINATE: u8 = 0b01;
+
+/// The job executor must stop running jobs.
+const STOP_JOBS: u8 = 0b10;
+
+/// A handle to interrupt the execution of a [`Context`](crate::Context) from any thread.
+///
+/// The handle is obtained with [`Context::interrupt_handle`](crate::Context::interrupt_handle),
+/// and can be cloned and sent to other threads, for example to implement a watchdog that stops
+/// scripts running for too long.
+#[derive(Debug, Clone, Default)]
+pub struct InterruptHandle {
+    flags: Arc<AtomicU8>,
+}
+
+impl InterruptHandle {
+    /// Requests the termination of the code currently running on the context.
+    ///
+    /// The VM checks for the request every few thousand instructions, and then interrupts the
+    /// execution with an [`EngineError::Terminated`] error, which cannot be caught by ECMAScript
+    /// code. If no code is running, the next execution is terminated shortly after it starts.
+    ///
+    /// The request is cleared once the error has been thrown, so the context can be used again.
+    ///
+    /// [`EngineError::Terminated`]: crate::error::EngineError::Terminated
+    pub fn terminate(&self) {
+        self.flags.fetch_or(TERMINATE, Ordering::Relaxed);
+    }
+
+    /// Requests the job executor to stop running jobs.
+    ///
+    /// The job that is currently running is not interrupted, but no other job is started, and
+    /// [`Context::run_jobs`](crate::Context::run_jobs) returns with the jobs that were not
+    /// started yet still enqueued. Native async jobs that were already started by the
+    /// [`SimpleJobExecutor`](crate::job::SimpleJobExecutor) are run to completion first, since
+    /// they cannot be put back in the queue.
+    ///
+    /// Custom [`JobExecutor`](crate::job::JobExecutor)s can honor the request by checking
+    /// [`InterruptHandle::take_job_stop_request`] between jobs.
+    pub fn request_job_stop(&self) {
+        self.flags.fetch_or(STOP_JOBS, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if [`InterruptHandle::request_job_stop`] was called since the last call to
+    /// this method, clearing the request.
+    #[must_use]
+    pub fn take_job_stop_request(&self) -> bool {
+        self.take(STOP_JOBS)
+    }
+
+    /// Returns `true` if [`InterruptHandle::terminate`] was called since the last call to this
+    /// method, clearing the request.
+    pub(crate) fn take_termination_request(&self) -> bool {
+        self.take(TERMINATE)
+    }
+
+    fn take(&self, flag: u8) -> bool {
+        self.flags.fetch_and(!flag, Ordering::Relaxed) & flag != 0
+    }
+}
@@ -35,6 +35,8 @@ pub(crate) use {
     inline_cache::InlineCache,
 };
 
+#[cfg(feature = "interrupt")]
+pub use interrupt::InterruptHandle;
 pub use runtime_limits::RuntimeLimits;
 pub use {
     call_frame::{CallFrame, GeneratorResumeKind},
@@ -48,6 +50,11 @@ mod completion_record;
 mod inline_cache;
 mod runtime_limits;
 
+#[cfg(feature = "interrupt")]
+mod instruction_budget;
+#[cfg(feature = "interrupt")]
+mod interrupt;
+
 pub(crate) mod opcode;
 pub(crate) mod shadow_stack;
 pub(crate) mod source_info;
@@ -55,6 +62,18 @@ pub(crate) mod source_info;
 #[cfg(feature = "flowgraph")]
 pub mod flowgraph;
 
+#[cfg(feature = "vm-stats")]
+pub mod stats;
+
+#[cfg(feature = "profiler")]
+pub mod profiler;
+
+#[cfg(feature = "coverage")]
+pub mod coverage;
+
+#[cfg(feature = "timeline")]
+pub mod timeline;
+
 #[cfg(test)]
 mod tests;
 
@@ -93,8 +112,30 @@ pub struct Vm {
 
     pub(crate) shadow_stack: ShadowStack,
 
+    #[cfg(feature = "interrupt")]
+    pub(crate) instruction_budget: instruction_budget::InstructionBudget,
+
+    #[cfg(feature = "interrupt")]
+    pub(crate) interrupt: InterruptHandle,
+
     #[cfg(feature = "trace")]
     pub(crate) trace: bool,
+
+    /// The opcode execution counters, if enabled.
+    #[cfg(feature = "vm-stats")]
+    pub(crate) stats: Option<stats::VmStats>,
+
+    /// The sampling profiler, if running.
+    #[cfg(feature = "profiler")]
+    pub(crate) profiler: Option<profiler::Profiler>,
+
+    /// The code coverage counters, if enabled.
+    #[cfg(feature = "coverage")]
+    pub(crate) coverage: Option<coverage::CoverageCollector>,
+
+    /// The timeline of the engine events, if enabled.
+    #[cfg(feature = "timeline")]
+    pub(crate) timeline: Option<timeline::TimelineRecorder>,
 }
 
 /// The stack holds the [`JsValue`]s that the VM is operating on.
@@ -423,8 +464,20 @@ impl Vm {
             runtime_limits: RuntimeLimits::default(),
             native_active_function: None,
             shadow_stack: ShadowStack::default(),
+            #[cfg(feature = "interrupt")]
+            instruction_budget: instruction_budget::InstructionBudget::default(),
+            #[cfg(feature = "interrupt")]
+            interrupt: InterruptHandle::default(),
             #[cfg(feature = "trace")]
             trace: false,
+            #[cfg(feature = "vm-stats")]
+            stats: None,
+            #[cfg(feature = "profiler")]
+            profiler: None,
+            #[cfg(feature = "coverage")]
+            coverage: None,
+            #[cfg(feature = "timeline")]
+            timeline: None,
         }
     }
 
@@ -644,15 +697,29 @@ impl Context {
     where
         F: FnOnce(&mut Context, Opcode) -> ControlFlow<CompletionRecord>,
     {
-        #[cfg(feature = "fuzz")]
+        #[cfg(feature = "interrupt")]
         {
-            use crate::error::EngineError;
-            if self.instructions_remaining == 0 {
-                return ControlFlow::Break(CompletionRecord::Throw(
-                    EngineError::NoInstructionsRemain.into(),
-                ));
+            if self.vm.instruction_budget.countdown == 0
+                && let Err(err) = self.check_instruction_budget()
+            {
+                return self.handle_error(err);
             }
-            self.instructions_remaining -= 1;
+            self.vm.instruction_budget.countdown -= 1;
+        }
+
+        #[cfg(feature = "vm-stats")]
+        if let Some(stats) = &mut self.vm.stats {
+            stats.record(&self.vm.frame.code_block, opcode);
+        }
+
+        #[cfg(feature = "profiler")]
+        if let Some(profiler) = &mut self.vm.profiler {
+            profiler.tick(&self.vm.shadow_stack);
+        }
+
+        #[cfg(feature = "coverage")]
+        if let Some(coverage) = &mut self.vm.coverage {
+            coverage.record(&self.vm.frame.code_block, self.vm.frame.pc);
         }
 
         #[cfg(feature = "trace")]
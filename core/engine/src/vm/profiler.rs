@@ -0,0 +1,167 @@
+//! A sampling profiler for the virtual machine.
+//!
+//! When started with [`Context::start_profiler`], the VM takes a sample of the call stack
+//! every `sample_interval` executed instructions. Using instructions instead of wall-clock
+//! time as the unit makes the resulting [`Profile`] deterministic, which is what we want when
+//! comparing two runs of the same script.
+//!
+//! [`Context::start_profiler`]: crate::Context::start_profiler
+
+use std::{cmp::Reverse, fmt::Write};
+
+use boa_string::JsString;
+use cow_utils::CowUtils;
+use rustc_hash::FxHashMap;
+
+use super::shadow_stack::{ShadowEntry, ShadowStack};
+
+/// A frame of a sampled call stack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SampledFrame {
+    Native(Option<JsString>),
+    Bytecode(JsString),
+}
+
+impl SampledFrame {
+    fn name(&self) -> String {
+        let (name, native) = match self {
+            Self::Native(name) => (name.as_ref(), true),
+            Self::Bytecode(name) => (Some(name), false),
+        };
+
+        let name = match name {
+            Some(name) if !name.is_empty() => name.to_std_string_escaped(),
+            _ => "<anonymous>".to_string(),
+        };
+
+        // NOTE: `;` is the separator of the folded stack format.
+        let mut name = name.cow_replace(';', ":").into_owned();
+
+        if native {
+            name.push_str(" (native)");
+        }
+        name
+    }
+}
+
+/// The state of a running profiler.
+#[derive(Debug)]
+pub(crate) struct Profiler {
+    sample_interval: u32,
+    countdown: u32,
+    samples: FxHashMap<Vec<SampledFrame>, u64>,
+}
+
+impl Profiler {
+    /// Creates a new profiler that samples every `sample_interval` executed instructions.
+    pub(crate) fn new(sample_interval: u32) -> Self {
+        let sample_interval = sample_interval.max(1);
+        Self {
+            sample_interval,
+            countdown: sample_interval,
+            samples: FxHashMap::default(),
+        }
+    }
+
+    /// Advances the profiler by one instruction, sampling the call stack if needed.
+    #[inline]
+    pub(crate) fn tick(&mut self, shadow_stack: &ShadowStack) {
+        self.countdown -= 1;
+        if self.countdown != 0 {
+            return;
+        }
+        self.countdown = self.sample_interval;
+
+        let stack = shadow_stack
+            .iter()
+            .map(|entry| match entry {
+                ShadowEntry::Native { function_name, .. } => {
+                    SampledFrame::Native(function_name.clone())
+                }
+                ShadowEntry::Bytecode { source_info, .. } => {
+                    SampledFrame::Bytecode(source_info.function_name().clone())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        *self.samples.entry(stack).or_default() += 1;
+    }
+
+    /// Consumes the profiler, returning the collected [`Profile`].
+    pub(crate) fn finish(self) -> Profile {
+        let mut stacks = self
+            .samples
+            .into_iter()
+            .map(|(frames, samples)| ProfileStack {
+                frames: frames.iter().map(SampledFrame::name).collect(),
+                samples,
+            })
+            .collect::<Vec<_>>();
+        stacks.sort_by_key(|stack| Reverse(stack.samples));
+
+        Profile {
+            sample_interval: self.sample_interval,
+            stacks,
+        }
+    }
+}
+
+/// The result of a profiling session.
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    sample_interval: u32,
+    stacks: Vec<ProfileStack>,
+}
+
+impl Profile {
+    /// Returns the number of executed instructions between two samples.
+    #[must_use]
+    pub const fn sample_interval(&self) -> u32 {
+        self.sample_interval
+    }
+
+    /// Returns the distinct sampled call stacks, sorted from the most to the least sampled.
+    #[must_use]
+    pub fn stacks(&self) -> &[ProfileStack] {
+        &self.stacks
+    }
+
+    /// Returns the total number of samples.
+    #[must_use]
+    pub fn total_samples(&self) -> u64 {
+        self.stacks.iter().map(ProfileStack::samples).sum()
+    }
+
+    /// Returns the profile in the folded stack format, one call stack per line.
+    ///
+    /// This is the format consumed by most flamegraph tools.
+    #[must_use]
+    pub fn to_folded(&self) -> String {
+        let mut result = String::new();
+        for stack in &self.stacks {
+            let _ = writeln!(result, "{} {}", stack.frames.join(";"), stack.samples);
+        }
+        result
+    }
+}
+
+/// A sampled call stack and the number of times it was sampled.
+#[derive(Debug, Clone)]
+pub struct ProfileStack {
+    frames: Vec<String>,
+    samples: u64,
+}
+
+impl ProfileStack {
+    /// Returns the names of the frames of the call stack, from the outermost to the innermost.
+    #[must_use]
+    pub fn frames(&self) -> &[String] {
+        &self.frames
+    }
+
+    /// Returns the number of times this call stack was sampled.
+    #[must_use]
+    pub const fn samples(&self) -> u64 {
+        self.samples
+    }
+}
@@ -110,6 +110,11 @@ impl ShadowStack {
             .push(ShadowEntry::Bytecode { pc: 0, source_info });
     }
 
+    #[cfg(feature = "profiler")]
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = &ShadowEntry> {
+        self.stack.iter()
+    }
+
     pub(crate) fn pop(&mut self) -> Option<ShadowEntry> {
         self.stack.pop()
     }
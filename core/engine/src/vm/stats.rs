@@ -0,0 +1,184 @@
+//! Opcode execution counters for the virtual machine.
+//!
+//! When enabled with [`Context::set_vm_stats`], the VM counts how many times each
+//! opcode has been executed, both globally and per [`CodeBlock`]. This is useful to
+//! find the hot instructions of a script, either to optimize user code or to guide
+//! work on the engine itself.
+//!
+//! [`Context::set_vm_stats`]: crate::Context::set_vm_stats
+
+use std::{
+    cmp::Reverse,
+    fmt::{self, Display},
+};
+
+use boa_gc::Gc;
+use boa_string::JsString;
+use rustc_hash::FxHashMap;
+
+use super::{CodeBlock, Opcode, SourcePath};
+
+/// Execution counters collected by the VM.
+#[derive(Debug, Clone)]
+pub struct VmStats {
+    opcodes: Box<[u64; 256]>,
+    code_blocks: FxHashMap<usize, CodeBlockStats>,
+}
+
+impl Default for VmStats {
+    fn default() -> Self {
+        Self {
+            opcodes: Box::new([0; 256]),
+            code_blocks: FxHashMap::default(),
+        }
+    }
+}
+
+impl VmStats {
+    /// Records the execution of `opcode` inside of `code_block`.
+    #[inline]
+    pub(crate) fn record(&mut self, code_block: &Gc<CodeBlock>, opcode: Opcode) {
+        self.opcodes[opcode as usize] += 1;
+
+        // NOTE: The code block is kept alive by the stored `Gc`, so its address
+        //       cannot be reused by another code block while it is in the map.
+        let key = std::ptr::from_ref::<CodeBlock>(code_block).addr();
+        self.code_blocks
+            .entry(key)
+            .or_insert_with(|| CodeBlockStats::new(code_block.clone()))
+            .record(opcode);
+    }
+
+    /// Returns the total number of executed instructions.
+    #[must_use]
+    pub fn total_instructions(&self) -> u64 {
+        self.opcodes.iter().sum()
+    }
+
+    /// Returns the name and execution count of every executed opcode,
+    /// sorted from the most to the least executed.
+    #[must_use]
+    pub fn opcodes(&self) -> Vec<(&'static str, u64)> {
+        sorted_opcode_counts(&self.opcodes)
+    }
+
+    /// Returns the statistics of every executed code block,
+    /// sorted from the most to the least executed.
+    #[must_use]
+    pub fn code_blocks(&self) -> Vec<&CodeBlockStats> {
+        let mut code_blocks = self.code_blocks.values().collect::<Vec<_>>();
+        code_blocks.sort_by_key(|code_block| Reverse(code_block.instructions));
+        code_blocks
+    }
+
+    /// Resets all the counters.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Display for VmStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const COLUMN_WIDTH: usize = 32;
+
+        writeln!(f, "{:-^width$}", " Opcodes ", width = COLUMN_WIDTH * 2)?;
+        for (name, count) in self.opcodes() {
+            writeln!(f, "{name:<COLUMN_WIDTH$}{count:>COLUMN_WIDTH$}")?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "{:-^width$}", " Code Blocks ", width = COLUMN_WIDTH * 2)?;
+        for code_block in self.code_blocks() {
+            let name = code_block.display_name();
+            let count = code_block.instructions();
+            writeln!(f, "{name:<COLUMN_WIDTH$}{count:>COLUMN_WIDTH$}")?;
+        }
+        writeln!(f)?;
+
+        write!(
+            f,
+            "{:<COLUMN_WIDTH$}{:>COLUMN_WIDTH$}",
+            "Total",
+            self.total_instructions()
+        )
+    }
+}
+
+/// Execution counters of a single [`CodeBlock`].
+#[derive(Debug, Clone)]
+pub struct CodeBlockStats {
+    code_block: Gc<CodeBlock>,
+    instructions: u64,
+    opcodes: Box<[u64; 256]>,
+}
+
+impl CodeBlockStats {
+    fn new(code_block: Gc<CodeBlock>) -> Self {
+        Self {
+            code_block,
+            instructions: 0,
+            opcodes: Box::new([0; 256]),
+        }
+    }
+
+    #[inline]
+    fn record(&mut self, opcode: Opcode) {
+        self.instructions += 1;
+        self.opcodes[opcode as usize] += 1;
+    }
+
+    /// Returns the name of the code block.
+    #[must_use]
+    pub fn name(&self) -> &JsString {
+        self.code_block.name()
+    }
+
+    /// Returns the source path of the code block.
+    #[must_use]
+    pub fn path(&self) -> &SourcePath {
+        self.code_block.path()
+    }
+
+    /// Returns the number of instructions executed in the code block.
+    #[must_use]
+    pub const fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Returns the name and execution count of every opcode executed in the code block,
+    /// sorted from the most to the least executed.
+    #[must_use]
+    pub fn opcodes(&self) -> Vec<(&'static str, u64)> {
+        sorted_opcode_counts(&self.opcodes)
+    }
+
+    /// Returns a human readable name of the code block, including its source path if any.
+    fn display_name(&self) -> String {
+        let name = self.name();
+        let name = if name.is_empty() {
+            "<anonymous>".to_string()
+        } else {
+            name.to_std_string_escaped()
+        };
+
+        match self.path() {
+            SourcePath::Path(path) => format!("{name} ({})", path.display()),
+            _ => name,
+        }
+    }
+}
+
+fn sorted_opcode_counts(counts: &[u64; 256]) -> Vec<(&'static str, u64)> {
+    let mut opcodes = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count != 0)
+        .map(|(opcode, count)| {
+            #[allow(clippy::cast_possible_truncation)]
+            let opcode = Opcode::decode(opcode as u8);
+            (opcode.as_str(), *count)
+        })
+        .collect::<Vec<_>>();
+    opcodes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    opcodes
+}
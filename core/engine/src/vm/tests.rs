@@ -3,7 +3,7 @@ use crate::vm::CallFrame;
 use crate::vm::call_frame::CallFrameLocation;
 use crate::vm::source_info::SourcePath;
 use crate::{
-    Context, JsNativeErrorKind, JsValue, NativeFunction, TestAction, js_string,
+    Context, JsError, JsNativeErrorKind, JsString, JsValue, NativeFunction, TestAction, js_string,
     property::Attribute, run_test_actions, run_test_actions_with,
 };
 use boa_ast::Position;
@@ -509,3 +509,578 @@ fn recursion_in_async_gen_throws_uncatchable_error() {
         RuntimeLimitError::Recursion,
     )]);
 }
+
+#[cfg(feature = "vm-stats")]
+#[test]
+fn vm_stats_counts_executed_opcodes() {
+    let context = &mut Context::default();
+    context.set_vm_stats(true);
+
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            function add(a, b) {
+                return a + b;
+            }
+            for (let i = 0; i < 10; i++) {
+                add(i, 1);
+            }
+        "#}))
+        .unwrap();
+
+    let stats = context.take_vm_stats().unwrap();
+    assert!(stats.total_instructions() > 0);
+
+    let add = stats
+        .code_blocks()
+        .into_iter()
+        .find(|code_block| code_block.name() == &js_string!("add"))
+        .expect("`add` should have been executed");
+    assert_eq!(
+        add.opcodes()
+            .iter()
+            .find(|(name, _)| *name == "Add")
+            .map(|(_, count)| *count),
+        Some(10)
+    );
+
+    // Taking the statistics resets them.
+    assert_eq!(context.vm_stats().unwrap().total_instructions(), 0);
+
+    context.set_vm_stats(false);
+    assert!(context.vm_stats().is_none());
+}
+
+#[cfg(feature = "profiler")]
+#[test]
+fn profiler_samples_call_stacks() {
+    let context = &mut Context::default();
+    context.start_profiler(1);
+    assert!(context.is_profiling());
+
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            function inner() {
+                let x = 0;
+                for (let i = 0; i < 100; i++) {
+                    x += i;
+                }
+                return x;
+            }
+            function outer() {
+                return inner();
+            }
+            outer();
+        "#}))
+        .unwrap();
+
+    let profile = context.stop_profiler().unwrap();
+    assert!(!context.is_profiling());
+    assert!(profile.total_samples() > 0);
+
+    let hottest = &profile.stacks()[0];
+    assert_eq!(hottest.frames(), ["<main>", "outer", "inner"]);
+    assert!(
+        profile
+            .to_folded()
+            .starts_with(&format!("<main>;outer;inner {}\n", hottest.samples()))
+    );
+}
+
+#[cfg(feature = "coverage")]
+#[test]
+fn coverage_counts_functions_and_blocks() {
+    let context = &mut Context::default();
+    context.start_coverage();
+
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            function called(x) {
+                if (x > 1) {
+                    return "big";
+                }
+                return "small";
+            }
+            function uncalled() {
+                return 1;
+            }
+            for (let i = 0; i < 3; i++) {
+                called(i);
+            }
+        "#}))
+        .unwrap();
+
+    let coverage = context.take_coverage().unwrap();
+    let function = |name: &str| {
+        coverage
+            .functions()
+            .iter()
+            .find(|function| function.name() == &js_string!(name))
+            .unwrap()
+    };
+
+    let called = function("called");
+    assert_eq!(called.count(), 3);
+    assert!(called.range().is_some());
+    let counts = called
+        .blocks()
+        .iter()
+        .map(crate::vm::coverage::BlockCoverage::count)
+        .collect::<Vec<_>>();
+    assert!(counts.contains(&1), "{counts:?}");
+    assert!(counts.contains(&2), "{counts:?}");
+
+    let uncalled = function("uncalled");
+    assert_eq!(uncalled.count(), 0);
+    assert!(uncalled.blocks().iter().all(|block| block.count() == 0));
+
+    // Taking the coverage resets the counters, but keeps collecting.
+    context.eval(Source::from_bytes("called(5)")).unwrap();
+    let coverage = context.stop_coverage().unwrap();
+    assert!(
+        coverage
+            .functions()
+            .iter()
+            .any(|function| function.count() == 1)
+    );
+    assert!(context.take_coverage().is_none());
+}
+
+#[cfg(feature = "coverage")]
+#[test]
+fn coverage_reports_lines_and_block_ranges() {
+    let context = &mut Context::default();
+    context.start_coverage();
+
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            function inner(n) {
+                let s = 0;
+                for (let i = 0; i < n; i++) {
+                    s += i;
+                }
+                return s;
+            }
+            inner(2000);
+            inner(2000);
+        "#}))
+        .unwrap();
+
+    let coverage = context.stop_coverage().unwrap();
+    let inner = coverage
+        .functions()
+        .iter()
+        .find(|function| function.name() == &js_string!("inner"))
+        .unwrap();
+    // Loop updates and jumps must not be credited to the line of the function declaration.
+    assert_eq!(
+        inner.lines(),
+        [(1, 2), (2, 2), (3, 4002), (4, 4000), (6, 2)]
+    );
+
+    let blocks = inner
+        .blocks()
+        .iter()
+        .filter_map(|block| Some((block.range()?, block.count())))
+        .map(|(range, count)| {
+            (
+                (range.start().line_number(), range.start().column_number()),
+                (range.end().line_number(), range.end().column_number()),
+                count,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        blocks,
+        [
+            ((1, 19), (3, 18), 2),
+            ((3, 28), (3, 28), 4000),
+            ((3, 21), (3, 21), 4002),
+            ((4, 9), (4, 9), 4000),
+            ((6, 12), (6, 12), 2),
+        ]
+    );
+    assert!(!inner.is_top_level());
+
+    let main = coverage
+        .functions()
+        .iter()
+        .find(|function| function.is_top_level())
+        .unwrap();
+    assert_eq!(main.lines(), [(8, 1), (9, 1)]);
+}
+
+#[cfg(feature = "coverage")]
+#[test]
+fn coverage_of_large_scripts() {
+    use std::fmt::Write;
+
+    // Aggregating the coverage must not scan every source position for every basic block.
+    const STATEMENTS: usize = 20_000;
+
+    let source = (0..STATEMENTS).fold(String::from("let x = 0, y;\n"), |mut source, i| {
+        let _ = writeln!(source, "if (x === {i}) {{ y = {i}; }}");
+        source
+    });
+
+    let context = &mut Context::default();
+    context.start_coverage();
+    context.eval(Source::from_bytes(&source)).unwrap();
+
+    let coverage = context.stop_coverage().unwrap();
+    let main = coverage
+        .functions()
+        .iter()
+        .find(|function| function.is_top_level())
+        .unwrap();
+    assert_eq!(main.lines().len(), STATEMENTS + 1);
+    assert!(main.lines().iter().all(|(_, count)| *count == 1));
+    assert!(main.blocks().len() > STATEMENTS * 2);
+}
+
+#[cfg(feature = "timeline")]
+#[test]
+fn timeline_records_engine_events() {
+    use crate::vm::timeline::TimelineCategory;
+
+    let context = &mut Context::default();
+    context.start_timeline(16);
+    assert!(context.is_recording_timeline());
+
+    context.timeline_mark("before");
+    context
+        .eval(Source::from_bytes("Promise.resolve().then(() => 1);"))
+        .unwrap();
+    context.run_jobs().unwrap();
+
+    let timeline = context.stop_timeline().unwrap();
+    assert!(!context.is_recording_timeline());
+
+    let categories = timeline
+        .events()
+        .iter()
+        .map(crate::vm::timeline::TimelineEvent::category)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        categories,
+        [
+            TimelineCategory::User,
+            TimelineCategory::Parse,
+            TimelineCategory::Compile,
+            TimelineCategory::Evaluate,
+            TimelineCategory::Job,
+        ]
+    );
+    assert_eq!(timeline.events()[0].name(), "before");
+
+    let trace = timeline.to_trace_events();
+    assert_eq!(trace["traceEvents"][0]["ph"], "i");
+    assert_eq!(trace["traceEvents"][3]["ph"], "X");
+    assert_eq!(trace["traceEvents"][3]["cat"], "evaluate");
+}
+
+#[cfg(feature = "timeline")]
+#[test]
+fn timeline_records_module_events() {
+    use crate::{builtins::promise::PromiseState, module::Module, vm::timeline::TimelineCategory};
+
+    let context = &mut Context::default();
+    context.start_timeline(16);
+
+    let module = Module::parse(Source::from_bytes("export let x = 1;"), None, context).unwrap();
+    let promise = module.load_link_evaluate(context);
+    context.run_jobs().unwrap();
+    assert!(matches!(promise.state(), PromiseState::Fulfilled(_)));
+
+    let timeline = context.stop_timeline().unwrap();
+    let events = timeline
+        .events()
+        .iter()
+        .filter(|event| event.category() != TimelineCategory::Job)
+        .map(|event| (event.category(), event.name()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            (TimelineCategory::Parse, "<module>"),
+            (TimelineCategory::Compile, "<module>"),
+            (TimelineCategory::Evaluate, "<module>"),
+        ]
+    );
+}
+
+#[cfg(feature = "timeline")]
+#[test]
+fn timeline_keeps_the_last_events() {
+    let context = &mut Context::default();
+    context.start_timeline(2);
+    for name in ["a", "b", "c"] {
+        context.timeline_mark(name);
+    }
+
+    let timeline = context.stop_timeline().unwrap();
+    let names = timeline
+        .events()
+        .iter()
+        .map(crate::vm::timeline::TimelineEvent::name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["b", "c"]);
+    assert_eq!(timeline.dropped(), 1);
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn instruction_budget_interrupts_execution() {
+    use crate::error::EngineError;
+
+    let context = &mut Context::default();
+    context.set_instruction_budget(Some(1000));
+
+    let err = context
+        .eval(Source::from_bytes(
+            "try { while (true) {} } catch { 'caught' }",
+        ))
+        .unwrap_err();
+    assert_eq!(
+        err.as_engine(),
+        Some(&EngineError::InstructionBudgetExhausted)
+    );
+    assert_eq!(context.instruction_budget(), Some(0));
+
+    // The context is still usable after resetting the budget.
+    context.set_instruction_budget(None);
+    let value = context.eval(Source::from_bytes("1 + 1")).unwrap();
+    assert_eq!(value, JsValue::new(2));
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn instruction_budget_counts_every_instruction() {
+    let source = indoc! {r#"
+        {
+            let sum = 0;
+            for (let i = 0; i < 5000; i++) {
+                sum += i;
+            }
+        }
+    "#};
+
+    let context = &mut Context::default();
+    context.set_instruction_budget(Some(1_000_000));
+    context.eval(Source::from_bytes(source)).unwrap();
+    let first = 1_000_000 - context.instruction_budget().unwrap();
+    context.eval(Source::from_bytes(source)).unwrap();
+    let second = 1_000_000 - first - context.instruction_budget().unwrap();
+
+    // The same script must consume the same number of instructions, even though the budget
+    // is only updated every few thousand instructions.
+    assert!(first > 5000);
+    assert_eq!(first, second);
+
+    // A budget larger than the check interval is exhausted exactly.
+    context.set_instruction_budget(Some(first * 2 + 1));
+    context.eval(Source::from_bytes(source)).unwrap();
+    context.eval(Source::from_bytes(source)).unwrap();
+    assert_eq!(context.instruction_budget(), Some(1));
+    assert!(context.eval(Source::from_bytes(source)).is_err());
+    assert_eq!(context.instruction_budget(), Some(0));
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn instruction_budget_handler_refills_budget() {
+    use std::{cell::Cell, rc::Rc};
+
+    let calls = Rc::new(Cell::new(0));
+    let context = &mut Context::default();
+    context.set_instruction_budget(Some(100));
+    context.set_instruction_budget_handler({
+        let calls = calls.clone();
+        move |_| {
+            calls.set(calls.get() + 1);
+            (calls.get() < 3).then_some(1000)
+        }
+    });
+
+    let value = context
+        .eval(Source::from_bytes(indoc! {r#"
+            let sum = 0;
+            for (let i = 0; i < 10; i++) {
+                sum += i;
+            }
+            sum;
+        "#}))
+        .unwrap();
+    assert_eq!(value, JsValue::new(45));
+    assert_eq!(calls.get(), 1);
+
+    assert!(context.eval(Source::from_bytes("while (true) {}")).is_err());
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn error_backtrace_frames() {
+    let context = &mut Context::default();
+    let error = context
+        .eval(Source::from_bytes(indoc! {r#"
+            function inner() {
+                throw new Error("inner");
+            }
+            function outer() {
+                inner();
+            }
+            outer();
+        "#}))
+        .unwrap_err();
+
+    let frames = error.backtrace().expect("thrown errors must have a backtrace");
+    let names = frames
+        .iter()
+        .map(|frame| frame.function_name().map(JsString::to_std_string_escaped))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            Some("inner".to_string()),
+            Some("outer".to_string()),
+            Some("<main>".to_string())
+        ]
+    );
+
+    assert!(frames.iter().all(|frame| !frame.is_native()));
+    assert_eq!(frames[0].position(), Some(Position::new(2, 11)));
+    assert_eq!(frames[1].position(), Some(Position::new(5, 10)));
+    assert_eq!(frames[2].position(), Some(Position::new(7, 6)));
+
+    let error = JsError::from_opaque(JsValue::new(1));
+    assert!(error.backtrace().is_none());
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn interrupt_handle_terminates_execution() {
+    use crate::error::EngineError;
+    use std::{thread, time::Duration};
+
+    let context = &mut Context::default();
+    let handle = context.interrupt_handle();
+    let watchdog = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        handle.terminate();
+    });
+
+    let err = context
+        .eval(Source::from_bytes(
+            "try { while (true) {} } catch { 'caught' }",
+        ))
+        .unwrap_err();
+    assert_eq!(err.as_engine(), Some(&EngineError::Terminated));
+    watchdog.join().unwrap();
+
+    // The termination request is cleared once handled.
+    let value = context.eval(Source::from_bytes("1 + 1")).unwrap();
+    assert_eq!(value, JsValue::new(2));
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn interrupt_handle_stops_jobs() {
+    let context = &mut Context::default();
+    context
+        .eval(Source::from_bytes(
+            "var done = false; Promise.resolve().then(() => { done = true; });",
+        ))
+        .unwrap();
+
+    context.interrupt_handle().request_job_stop();
+    context.run_jobs().unwrap();
+    let done = context.eval(Source::from_bytes("done")).unwrap();
+    assert_eq!(done, JsValue::new(false));
+
+    context.run_jobs().unwrap();
+    let done = context.eval(Source::from_bytes("done")).unwrap();
+    assert_eq!(done, JsValue::new(true));
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn interrupt_handle_finishes_started_async_jobs() {
+    use crate::{builtins::promise::PromiseState, object::builtins::JsPromise};
+
+    let context = &mut Context::default();
+    let promise = JsPromise::from_async_fn(
+        async |context| {
+            context.borrow().interrupt_handle().request_job_stop();
+            futures_lite::future::yield_now().await;
+            Ok(JsValue::new(1))
+        },
+        context,
+    );
+
+    // The async job was already started when the stop was requested, so it must settle its
+    // promise instead of being dropped.
+    context.run_jobs().unwrap();
+    assert_eq!(promise.state(), PromiseState::Fulfilled(JsValue::new(1)));
+}
+
+#[cfg(feature = "interrupt")]
+#[test]
+fn interrupt_handle_stops_jobs_from_a_job() {
+    let context = &mut Context::default();
+    context
+        .register_global_callable(
+            js_string!("stopJobs"),
+            0,
+            NativeFunction::from_fn_ptr(|_, _, context| {
+                context.interrupt_handle().request_job_stop();
+                Ok(JsValue::undefined())
+            }),
+        )
+        .unwrap();
+    context
+        .eval(Source::from_bytes(indoc! {r#"
+            var log = [];
+            Promise.resolve()
+                .then(() => { log.push(1); stopJobs(); })
+                .then(() => log.push(4));
+            Promise.resolve().then(() => log.push(2));
+            Promise.resolve().then(() => log.push(3));
+        "#}))
+        .unwrap();
+
+    // The jobs already queued when the stop was requested are not run.
+    context.run_jobs().unwrap();
+    let log = context.eval(Source::from_bytes("log.join()")).unwrap();
+    assert_eq!(log, JsValue::new(js_string!("1")));
+
+    // They stay queued in order, before the jobs enqueued by the job that requested the stop.
+    context.run_jobs().unwrap();
+    let log = context.eval(Source::from_bytes("log.join()")).unwrap();
+    assert_eq!(log, JsValue::new(js_string!("1,2,3,4")));
+}
+
+#[test]
+fn backtrace_positions_of_statements() {
+    // Errors thrown by code without a position of its own, like a reference to an undeclared
+    // variable, are reported at the start of the expression of their statement.
+    let cases = [
+        ("missing;", (1, 1)),
+        ("function f() {\n    return 1 + missing;\n}\nf();", (2, 12)),
+        ("if (missing) {}", (1, 5)),
+        ("let x = missing;", (1, 9)),
+        ("var y = 1, z = missing;", (1, 16)),
+        ("while (missing) {}", (1, 8)),
+        ("for (let i = 0; missing; i++) {}", (1, 17)),
+        ("do {} while (missing);", (1, 14)),
+    ];
+
+    for (source, (line, column)) in cases {
+        let context = &mut Context::default();
+        let error = context.eval(Source::from_bytes(source)).unwrap_err();
+        let frames = error.backtrace().unwrap();
+        assert_eq!(
+            frames[0].position(),
+            Some(Position::new(line, column)),
+            "{source}"
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! A timeline of the events of the engine.
+//!
+//! When started with [`Context::start_timeline`], the engine records when scripts and modules are
+//! parsed, compiled and evaluated, and when jobs are run, together with any user marks added with
+//! [`Context::timeline_mark`]. Only the most recent events are kept, so the timeline can stay
+//! enabled for long running programs.
+//!
+//! The resulting [`Timeline`] can be exported in the [Trace Event Format][trace], to be
+//! displayed by tools like `chrome://tracing` or [Perfetto](https://ui.perfetto.dev/).
+//!
+//! [trace]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+//! [`Context::start_timeline`]: crate::Context::start_timeline
+//! [`Context::timeline_mark`]: crate::Context::timeline_mark
+
+use std::{collections::VecDeque, time::Duration};
+
+use serde_json::{Value, json};
+
+use crate::sys::time::Instant;
+
+/// The state of a running timeline.
+#[derive(Debug)]
+pub(crate) struct TimelineRecorder {
+    origin: Instant,
+    capacity: usize,
+    events: VecDeque<TimelineEvent>,
+    dropped: u64,
+}
+
+impl TimelineRecorder {
+    /// Creates a new recorder that keeps at most the last `capacity` events.
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            origin: Instant::now(),
+            capacity,
+            events: VecDeque::with_capacity(capacity.min(1024)),
+            dropped: 0,
+        }
+    }
+
+    /// Returns the time elapsed since the timeline was started.
+    pub(crate) fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+
+    /// Records an event that started at `start` and ends now.
+    pub(crate) fn record(&mut self, category: TimelineCategory, name: String, start: Duration) {
+        let duration = self.now().saturating_sub(start);
+        self.push(TimelineEvent {
+            category,
+            name,
+            start,
+            duration,
+        });
+    }
+
+    /// Records an event without duration.
+    pub(crate) fn mark(&mut self, name: String) {
+        let start = self.now();
+        self.push(TimelineEvent {
+            category: TimelineCategory::User,
+            name,
+            start,
+            duration: Duration::ZERO,
+        });
+    }
+
+    fn push(&mut self, event: TimelineEvent) {
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+            self.dropped += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// Consumes the recorder, returning the recorded [`Timeline`].
+    pub(crate) fn finish(self) -> Timeline {
+        let mut events = Vec::from(self.events);
+        // NOTE: Events are recorded when they end, so nested events come before their parent.
+        events.sort_by_key(|event| event.start);
+
+        Timeline {
+            events,
+            dropped: self.dropped,
+        }
+    }
+}
+
+/// The kind of a [`TimelineEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineCategory {
+    /// A script or module was parsed.
+    Parse,
+    /// A script or module was compiled to bytecode.
+    Compile,
+    /// A script or module was evaluated.
+    Evaluate,
+    /// A job was run.
+    Job,
+    /// A mark added with [`Context::timeline_mark`](crate::Context::timeline_mark).
+    User,
+}
+
+impl TimelineCategory {
+    /// Returns the name of the category.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Compile => "compile",
+            Self::Evaluate => "evaluate",
+            Self::Job => "job",
+            Self::User => "user",
+        }
+    }
+}
+
+/// An event of the timeline.
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    category: TimelineCategory,
+    name: String,
+    start: Duration,
+    duration: Duration,
+}
+
+impl TimelineEvent {
+    /// Returns the category of the event.
+    #[must_use]
+    pub const fn category(&self) -> TimelineCategory {
+        self.category
+    }
+
+    /// Returns the name of the event.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the start time of the event, relative to the start of the timeline.
+    #[must_use]
+    pub const fn start(&self) -> Duration {
+        self.start
+    }
+
+    /// Returns the duration of the event, which is zero for marks.
+    #[must_use]
+    pub const fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// The result of a timeline recording.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    events: Vec<TimelineEvent>,
+    dropped: u64,
+}
+
+impl Timeline {
+    /// Returns the recorded events, sorted by start time.
+    #[must_use]
+    pub fn events(&self) -> &[TimelineEvent] {
+        &self.events
+    }
+
+    /// Returns the number of old events that were discarded because the timeline was full.
+    #[must_use]
+    pub const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Returns the timeline in the Trace Event Format, as a JSON object.
+    #[must_use]
+    pub fn to_trace_events(&self) -> Value {
+        let events = self
+            .events
+            .iter()
+            .map(|event| {
+                #[allow(clippy::cast_precision_loss)]
+                let start = event.start.as_nanos() as f64 / 1000.0;
+                if event.category == TimelineCategory::User {
+                    json!({
+                        "name": event.name,
+                        "cat": event.category.as_str(),
+                        "ph": "i",
+                        "s": "g",
+                        "ts": start,
+                        "pid": 1,
+                        "tid": 1,
+                    })
+                } else {
+                    #[allow(clippy::cast_precision_loss)]
+                    let duration = event.duration.as_nanos() as f64 / 1000.0;
+                    json!({
+                        "name": event.name,
+                        "cat": event.category.as_str(),
+                        "ph": "X",
+                        "ts": start,
+                        "dur": duration,
+                        "pid": 1,
+                        "tid": 1,
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+    }
+}
@@ -83,6 +83,48 @@ pub trait Logger: Trace {
     /// # Errors
     /// Returning an error will throw an exception in JavaScript.
     fn error(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()>;
+
+    /// Start a profile (`console.profile`). By default, does nothing.
+    ///
+    /// # Errors
+    /// Returning an error will throw an exception in JavaScript.
+    fn profile(
+        &self,
+        _label: Option<String>,
+        _state: &ConsoleState,
+        _context: &mut Context,
+    ) -> JsResult<()> {
+        Ok(())
+    }
+
+    /// Stop a profile (`console.profileEnd`). By default, does nothing.
+    ///
+    /// # Errors
+    /// Returning an error will throw an exception in JavaScript.
+    fn profile_end(
+        &self,
+        _label: Option<String>,
+        _state: &ConsoleState,
+        _context: &mut Context,
+    ) -> JsResult<()> {
+        Ok(())
+    }
+
+    /// Record the elapsed time of a timer, in milliseconds (`console.timeLog` and
+    /// `console.timeEnd`). This is called in addition to logging the formatted message,
+    /// for backends that want structured timing data. By default, does nothing.
+    ///
+    /// # Errors
+    /// Returning an error will throw an exception in JavaScript.
+    fn time_record(
+        &self,
+        _label: String,
+        _elapsed: u128,
+        _state: &ConsoleState,
+        _context: &mut Context,
+    ) -> JsResult<()> {
+        Ok(())
+    }
 }
 
 /// The default implementation for logging from the console.
@@ -429,6 +471,16 @@ impl Console {
             js_string!("timeEnd"),
             0,
         )
+        .function(
+            console_method(Self::profile, state.clone(), logger.clone()),
+            js_string!("profile"),
+            0,
+        )
+        .function(
+            console_method(Self::profile_end, state.clone(), logger.clone()),
+            js_string!("profileEnd"),
+            0,
+        )
         .function(
             console_method(Self::dir, state.clone(), logger.clone()),
             js_string!("dir"),
@@ -770,6 +822,12 @@ impl Console {
                 concat = concat + " " + &msg.display().to_string();
             }
             logger.log(concat, &console.state, context)?;
+            logger.time_record(
+                label.to_std_string_escaped(),
+                time - t,
+                &console.state,
+                context,
+            )?;
         } else {
             logger.warn(
                 format!("Timer '{}' doesn't exist", label.to_std_string_escaped()),
@@ -814,6 +872,12 @@ impl Console {
                 &console.state,
                 context,
             )?;
+            logger.time_record(
+                label.to_std_string_escaped(),
+                time - t,
+                &console.state,
+                context,
+            )?;
         } else {
             logger.warn(
                 format!("Timer '{}' doesn't exist", label.to_std_string_escaped()),
@@ -893,6 +957,62 @@ impl Console {
         Ok(JsValue::undefined())
     }
 
+    /// `console.profile(label)`
+    ///
+    /// Starts recording a profile, if the logger supports it.
+    ///
+    /// This is not part of the WHATWG `console` specification, but is supported by most runtimes.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/profile_static
+    fn profile(
+        _: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        logger: &impl Logger,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let label = match args.first() {
+            Some(value) if !value.is_undefined() => {
+                Some(value.to_string(context)?.to_std_string_escaped())
+            }
+            _ => None,
+        };
+
+        logger.profile(label, &console.state, context)?;
+        Ok(JsValue::undefined())
+    }
+
+    /// `console.profileEnd(label)`
+    ///
+    /// Stops recording a profile, if the logger supports it.
+    ///
+    /// This is not part of the WHATWG `console` specification, but is supported by most runtimes.
+    ///
+    /// More information:
+    ///  - [MDN documentation][mdn]
+    ///
+    /// [mdn]: https://developer.mozilla.org/en-US/docs/Web/API/console/profileEnd_static
+    fn profile_end(
+        _: &JsValue,
+        args: &[JsValue],
+        console: &Self,
+        logger: &impl Logger,
+        context: &mut Context,
+    ) -> JsResult<JsValue> {
+        let label = match args.first() {
+            Some(value) if !value.is_undefined() => {
+                Some(value.to_string(context)?.to_std_string_escaped())
+            }
+            _ => None,
+        };
+
+        logger.profile_end(label, &console.state, context)?;
+        Ok(JsValue::undefined())
+    }
+
     /// `console.dir(item, options)`
     ///
     /// Prints info about item
@@ -137,6 +137,34 @@ impl Logger for RecordingLogger {
     fn error(&self, msg: String, state: &ConsoleState, context: &mut Context) -> JsResult<()> {
         self.log(msg, state, context)
     }
+
+    fn profile(
+        &self,
+        label: Option<String>,
+        state: &ConsoleState,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        self.log(format!("profile {label:?}"), state, context)
+    }
+
+    fn profile_end(
+        &self,
+        label: Option<String>,
+        state: &ConsoleState,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        self.log(format!("profileEnd {label:?}"), state, context)
+    }
+
+    fn time_record(
+        &self,
+        label: String,
+        _: u128,
+        state: &ConsoleState,
+        context: &mut Context,
+    ) -> JsResult<()> {
+        self.log(format!("timeRecord {label}"), state, context)
+    }
 }
 
 /// Harness methods to be used in JS tests.
@@ -375,3 +403,38 @@ fn trace_with_stack_trace() {
         "# }
     );
 }
+
+#[test]
+fn profile_and_timers_reach_the_logger() {
+    let mut context = Context::default();
+    let logger = RecordingLogger::default();
+    Console::register_with_logger(logger.clone(), &mut context).unwrap();
+
+    run_test_actions_with(
+        [TestAction::run(indoc! {r#"
+            console.profile("work");
+            console.profileEnd("work");
+            console.profile();
+            console.profileEnd();
+            console.time("t");
+            console.timeEnd("t");
+        "#})],
+        &mut context,
+    );
+
+    let logs = logger.log.borrow().clone();
+    let logs = logs
+        .lines()
+        .filter(|line| !line.starts_with("t: "))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        logs,
+        [
+            r#"profile Some("work")"#,
+            r#"profileEnd Some("work")"#,
+            "profile None",
+            "profileEnd None",
+            "timeRecord t",
+        ]
+    );
+}